@@ -22,6 +22,15 @@ pub enum ErrorKind {
     MoreThanThreeIndexedParams,
     WrongNumberOfParams,
     AlreadyDefined,
+    /// Floating-point literals are not supported.
+    FloatLiteral,
+    /// A static assertion failed, carrying its message if one was given.
+    StaticAssertionFailed(Option<String>),
+    /// An expression that must be evaluated at compile time cannot be.
+    NonConstExpression,
+    /// A language feature that can be parsed but is not supported by the
+    /// analyzer or the compiler yet.
+    NotSupported(&'static str),
 }
 
 #[derive(Debug, PartialEq)]
@@ -161,6 +170,38 @@ impl SemanticError {
         }
     }
 
+    /// Create a new error with kind `FloatLiteral`
+    pub fn float_literal() -> Self {
+        SemanticError {
+            kind: ErrorKind::FloatLiteral,
+            context: vec![],
+        }
+    }
+
+    /// Create a new error with kind `StaticAssertionFailed`
+    pub fn static_assertion_failed(msg: Option<String>) -> Self {
+        SemanticError {
+            kind: ErrorKind::StaticAssertionFailed(msg),
+            context: vec![],
+        }
+    }
+
+    /// Create a new error with kind `NonConstExpression`
+    pub fn non_const_expression() -> Self {
+        SemanticError {
+            kind: ErrorKind::NonConstExpression,
+            context: vec![],
+        }
+    }
+
+    /// Create a new error with kind `NotSupported`
+    pub fn not_supported(feature: &'static str) -> Self {
+        SemanticError {
+            kind: ErrorKind::NotSupported(feature),
+            context: vec![],
+        }
+    }
+
     /// Maps the error to a new error that contains the given span in its
     /// context.
     pub fn with_context(mut self, span: Span) -> Self {
@@ -8,6 +8,7 @@
 pub mod builtins;
 pub mod errors;
 pub mod namespace;
+pub mod naming;
 mod traversal;
 
 use crate::errors::SemanticError;
@@ -30,8 +31,8 @@ use fe_parser::span::{
 };
 use std::cell::RefCell;
 use std::collections::{
+    BTreeSet,
     HashMap,
-    HashSet,
 };
 use std::rc::Rc;
 
@@ -73,13 +74,13 @@ pub struct ContractAttributes {
     /// Events that have been defined by the user.
     pub events: Vec<Event>,
     /// Static strings that the contract defines
-    pub string_literals: HashSet<String>,
+    pub string_literals: BTreeSet<String>,
     /// Structs that have been defined by the user
     pub structs: Vec<Struct>,
     /// External contracts that may be called from within this contract.
     pub external_contracts: Vec<Contract>,
     /// Names of contracts that have been created inside of this contract.
-    pub created_contracts: HashSet<String>,
+    pub created_contracts: BTreeSet<String>,
 }
 
 impl From<Shared<ContractScope>> for ContractAttributes {
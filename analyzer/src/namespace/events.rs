@@ -9,10 +9,17 @@ pub struct Event {
     pub topic: String,
     fields: Vec<FixedSize>,
     indexed_fields: Vec<usize>,
+    /// True if the event is logged without its topic.
+    pub anonymous: bool,
 }
 
 impl Event {
-    pub fn new(name: &str, fields: Vec<FixedSize>, indexed_fields: Vec<usize>) -> Self {
+    pub fn new(
+        name: &str,
+        fields: Vec<FixedSize>,
+        indexed_fields: Vec<usize>,
+        anonymous: bool,
+    ) -> Self {
         let abi_fields = fields
             .iter()
             .map(|field| field.abi_name())
@@ -23,6 +30,7 @@ impl Event {
             topic,
             fields,
             indexed_fields,
+            anonymous,
         }
     }
 
@@ -85,6 +93,7 @@ mod tests {
                 FixedSize::Base(Base::Bool),
             ],
             vec![1],
+            false,
         );
 
         assert_eq!(
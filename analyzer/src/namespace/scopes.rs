@@ -4,11 +4,13 @@ use crate::namespace::types::{
     FixedSize,
     Type,
 };
+use fe_parser::const_eval::ConstValue;
 use std::cell::RefCell;
-use std::collections::hash_map::Entry;
+use std::collections::btree_map::Entry;
 use std::collections::{
+    BTreeMap,
+    BTreeSet,
     HashMap,
-    HashSet,
 };
 use std::rc::Rc;
 
@@ -30,18 +32,19 @@ pub struct ContractFieldDef {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ModuleScope {
-    pub type_defs: HashMap<String, Type>,
+    pub type_defs: BTreeMap<String, Type>,
+    pub const_defs: HashMap<String, ConstValue>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ContractScope {
     pub parent: Shared<ModuleScope>,
     pub interface: Vec<String>,
-    pub event_defs: HashMap<String, Event>,
-    pub field_defs: HashMap<String, ContractFieldDef>,
-    pub function_defs: HashMap<String, ContractFunctionDef>,
-    pub string_defs: HashSet<String>,
-    pub created_contracts: HashSet<String>,
+    pub event_defs: BTreeMap<String, Event>,
+    pub field_defs: BTreeMap<String, ContractFieldDef>,
+    pub function_defs: BTreeMap<String, ContractFunctionDef>,
+    pub string_defs: BTreeSet<String>,
+    pub created_contracts: BTreeSet<String>,
     num_fields: usize,
 }
 
@@ -49,7 +52,7 @@ pub struct ContractScope {
 pub struct BlockScope {
     pub name: String,
     pub parent: BlockScopeParent,
-    pub variable_defs: HashMap<String, FixedSize>,
+    pub variable_defs: BTreeMap<String, FixedSize>,
     pub typ: BlockScopeType,
 }
 
@@ -71,6 +74,7 @@ pub enum BlockScopeType {
     Function,
     IfElse,
     Loop,
+    Unchecked,
 }
 
 impl Scope {
@@ -86,7 +90,8 @@ impl Scope {
 impl ModuleScope {
     pub fn new() -> Shared<Self> {
         Rc::new(RefCell::new(ModuleScope {
-            type_defs: HashMap::new(),
+            type_defs: BTreeMap::new(),
+            const_defs: HashMap::new(),
         }))
     }
 
@@ -104,18 +109,28 @@ impl ModuleScope {
     pub fn get_type_def(&self, name: &str) -> Option<Type> {
         self.type_defs.get(name).map(|typ| typ.to_owned())
     }
+
+    /// Add the value of a constant definition to the scope.
+    pub fn add_const_def(&mut self, name: &str, value: ConstValue) -> Result<(), SemanticError> {
+        if self.const_defs.contains_key(name) {
+            return Err(SemanticError::already_defined());
+        }
+
+        self.const_defs.insert(name.to_owned(), value);
+        Ok(())
+    }
 }
 
 impl ContractScope {
     pub fn new(parent: Shared<ModuleScope>) -> Shared<Self> {
         Rc::new(RefCell::new(ContractScope {
             parent,
-            function_defs: HashMap::new(),
-            event_defs: HashMap::new(),
-            field_defs: HashMap::new(),
-            string_defs: HashSet::new(),
+            function_defs: BTreeMap::new(),
+            event_defs: BTreeMap::new(),
+            field_defs: BTreeMap::new(),
+            string_defs: BTreeSet::new(),
             interface: vec![],
-            created_contracts: HashSet::new(),
+            created_contracts: BTreeSet::new(),
             num_fields: 0,
         }))
     }
@@ -212,7 +227,7 @@ impl BlockScope {
         Rc::new(RefCell::new(BlockScope {
             name: name.to_owned(),
             parent,
-            variable_defs: HashMap::new(),
+            variable_defs: BTreeMap::new(),
             typ,
         }))
     }
@@ -1,9 +1,6 @@
 use crate::errors::SemanticError;
 use fe_parser::ast as fe;
-use std::collections::{
-    BTreeMap,
-    HashMap,
-};
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::num::{
     IntErrorKind,
@@ -715,14 +712,14 @@ impl AbiEncoding for Contract {
 }
 
 pub fn type_desc_fixed_size(
-    defs: &HashMap<String, Type>,
+    defs: &BTreeMap<String, Type>,
     typ: &fe::TypeDesc,
 ) -> Result<FixedSize, SemanticError> {
     FixedSize::try_from(type_desc(defs, typ)?)
 }
 
 pub fn type_desc_base(
-    defs: &HashMap<String, Type>,
+    defs: &BTreeMap<String, Type>,
     typ: &fe::TypeDesc,
 ) -> Result<Base, SemanticError> {
     match type_desc(defs, typ)? {
@@ -731,7 +728,7 @@ pub fn type_desc_base(
     }
 }
 
-pub fn type_desc(defs: &HashMap<String, Type>, typ: &fe::TypeDesc) -> Result<Type, SemanticError> {
+pub fn type_desc(defs: &BTreeMap<String, Type>, typ: &fe::TypeDesc) -> Result<Type, SemanticError> {
     match typ {
         fe::TypeDesc::Base { base: "u256" } => Ok(Type::Base(U256)),
         fe::TypeDesc::Base { base: "u128" } => Ok(Type::Base(Base::Numeric(Integer::U128))),
@@ -762,6 +759,29 @@ pub fn type_desc(defs: &HashMap<String, Type>, typ: &fe::TypeDesc) -> Result<Typ
             inner: type_desc_base(defs, &typ.node)?,
             size: *dimension,
         })),
+        fe::TypeDesc::Int { signed, bits } => match (signed, bits) {
+            (false, 256) => Ok(Type::Base(U256)),
+            (false, 128) => Ok(Type::Base(Base::Numeric(Integer::U128))),
+            (false, 64) => Ok(Type::Base(Base::Numeric(Integer::U64))),
+            (false, 32) => Ok(Type::Base(Base::Numeric(Integer::U32))),
+            (false, 16) => Ok(Type::Base(Base::Numeric(Integer::U16))),
+            (false, 8) => Ok(Type::Base(Base::Numeric(Integer::U8))),
+            (true, 256) => Ok(Type::Base(Base::Numeric(Integer::I256))),
+            (true, 128) => Ok(Type::Base(Base::Numeric(Integer::I128))),
+            (true, 64) => Ok(Type::Base(Base::Numeric(Integer::I64))),
+            (true, 32) => Ok(Type::Base(Base::Numeric(Integer::I32))),
+            (true, 16) => Ok(Type::Base(Base::Numeric(Integer::I16))),
+            (true, 8) => Ok(Type::Base(Base::Numeric(Integer::I8))),
+            _ => Err(SemanticError::not_supported(
+                "integer widths other than 8, 16, 32, 64, 128 and 256 bits",
+            )),
+        },
+        // The parser only accepts these in the signatures of generic
+        // functions, which are rejected before their types are resolved.
+        fe::TypeDesc::GenericArray { .. } => Err(SemanticError::not_supported(
+            "arrays with a generic dimension",
+        )),
+        fe::TypeDesc::Never => Err(SemanticError::not_supported("the never type")),
         fe::TypeDesc::Map { from, to } => Ok(Type::Map(Map {
             key: type_desc_base(defs, &from.node)?,
             value: Box::new(type_desc(defs, &to.node)?),
@@ -0,0 +1,255 @@
+//! Lint pass that checks declaration names against casing conventions.
+
+use fe_parser::ast as fe;
+use fe_parser::span::{
+    Span,
+    Spanned,
+};
+
+/// A casing convention for identifiers.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Case {
+    /// e.g. "GuestBook"
+    Pascal,
+    /// e.g. "sign_book"
+    Snake,
+    /// e.g. "MAX_ENTRIES"
+    ScreamingSnake,
+}
+
+impl Case {
+    /// Return true if `name` follows the convention.  Leading underscores are
+    /// ignored.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.trim_start_matches('_');
+        let mut chars = name.chars();
+
+        match (self, chars.next()) {
+            (_, None) => true,
+            (Case::Pascal, Some(first)) => {
+                first.is_ascii_uppercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            (Case::Snake, Some(_)) => name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            (Case::ScreamingSnake, Some(_)) => name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Case::Pascal => "PascalCase",
+            Case::Snake => "snake_case",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+}
+
+/// The convention enforced for each kind of declaration.  `None` disables the
+/// check for that kind.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamingConfig {
+    pub contracts: Option<Case>,
+    pub structs: Option<Case>,
+    pub type_aliases: Option<Case>,
+    pub events: Option<Case>,
+    pub functions: Option<Case>,
+    pub constants: Option<Case>,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            contracts: Some(Case::Pascal),
+            structs: Some(Case::Pascal),
+            type_aliases: Some(Case::Pascal),
+            events: Some(Case::Pascal),
+            functions: Some(Case::Snake),
+            constants: Some(Case::ScreamingSnake),
+        }
+    }
+}
+
+/// A warning produced when a declaration name does not follow the configured
+/// convention.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamingWarning<'a> {
+    pub name: &'a str,
+    pub expected: Case,
+    pub span: Span,
+}
+
+impl<'a> NamingWarning<'a> {
+    pub fn message(&self) -> String {
+        format!(
+            "\"{}\" should be written in {}",
+            self.name,
+            self.expected.description(),
+        )
+    }
+}
+
+/// Return a warning for every declaration in `module` whose name violates the
+/// convention configured for its kind.
+pub fn check_naming<'a>(module: &fe::Module<'a>, config: &NamingConfig) -> Vec<NamingWarning<'a>> {
+    let mut warnings = vec![];
+
+    for stmt in module.body.iter() {
+        match &stmt.node {
+            fe::ModuleStmt::ContractDef { name, body, .. } => {
+                check_name(&mut warnings, name, config.contracts);
+
+                for stmt in body.iter() {
+                    match &stmt.node {
+                        fe::ContractStmt::ContractField {
+                            qual:
+                                Some(Spanned {
+                                    node: fe::ContractFieldQual::Const,
+                                    ..
+                                }),
+                            name,
+                            ..
+                        } => check_name(&mut warnings, name, config.constants),
+                        fe::ContractStmt::EventDef { name, .. } => {
+                            check_name(&mut warnings, name, config.events)
+                        }
+                        fe::ContractStmt::FuncDef { name, .. } => {
+                            check_name(&mut warnings, name, config.functions)
+                        }
+                        fe::ContractStmt::StructDef { name, .. } => {
+                            check_name(&mut warnings, name, config.structs)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            fe::ModuleStmt::StructDef { name, .. } => {
+                check_name(&mut warnings, name, config.structs)
+            }
+            fe::ModuleStmt::TypeDef { name, .. } => {
+                check_name(&mut warnings, name, config.type_aliases)
+            }
+            fe::ModuleStmt::ConstDef { name, .. } => {
+                check_name(&mut warnings, name, config.constants)
+            }
+            _ => {}
+        }
+    }
+
+    warnings
+}
+
+fn check_name<'a>(
+    warnings: &mut Vec<NamingWarning<'a>>,
+    name: &Spanned<&'a str>,
+    expected: Option<Case>,
+) {
+    if let Some(expected) = expected {
+        if !expected.matches(name.node) {
+            warnings.push(NamingWarning {
+                name: name.node,
+                expected,
+                span: name.span,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fe_parser::get_parse_tokens;
+    use fe_parser::parsers::file_input;
+    use rstest::rstest;
+
+    fn check_src(src: &str, config: &NamingConfig) -> Vec<(String, Case)> {
+        let tokens = get_parse_tokens(src).expect("Couldn't tokenize module");
+        let module = file_input(&tokens[..])
+            .expect("Couldn't build module AST")
+            .1;
+
+        check_naming(&module.node, config)
+            .into_iter()
+            .map(|warning| (warning.name.to_string(), warning.expected))
+            .collect()
+    }
+
+    #[rstest(
+        name,
+        case,
+        expected,
+        case("GuestBook", Case::Pascal, true),
+        case("guest_book", Case::Pascal, false),
+        case("Guest_Book", Case::Pascal, false),
+        case("sign_book2", Case::Snake, true),
+        case("_private", Case::Snake, true),
+        case("signBook", Case::Snake, false),
+        case("MAX_ENTRIES", Case::ScreamingSnake, true),
+        case("Max_Entries", Case::ScreamingSnake, false)
+    )]
+    fn case_matches(name: &str, case: Case, expected: bool) {
+        assert_eq!(case.matches(name), expected)
+    }
+
+    #[test]
+    fn snake_case_contract_warns() {
+        let src = "contract guest_book:\n    pub def sign():\n        pass\n";
+        let tokens = get_parse_tokens(src).unwrap();
+        let module = file_input(&tokens[..]).unwrap().1;
+        let warnings = check_naming(&module.node, &NamingConfig::default());
+
+        assert_eq!(
+            warnings,
+            vec![NamingWarning {
+                name: "guest_book",
+                expected: Case::Pascal,
+                span: Span::new(9, 19),
+            }],
+        );
+        assert_eq!(
+            warnings[0].message(),
+            "\"guest_book\" should be written in PascalCase",
+        );
+    }
+
+    #[test]
+    fn pascal_case_contract_does_not_warn() {
+        let src = "contract GuestBook:\n    pub def sign():\n        pass\n";
+        assert_eq!(check_src(src, &NamingConfig::default()), vec![]);
+    }
+
+    #[test]
+    fn declaration_kinds() {
+        let src = "\
+type addressList = address[10]
+const maxEntries: u256 = 10
+struct my_point:
+    x: u256
+contract Foo:
+    const max_size: u256
+    event signed:
+        idx who: address
+    pub def signBook():
+        pass
+";
+        assert_eq!(
+            check_src(src, &NamingConfig::default()),
+            vec![
+                ("addressList".to_string(), Case::Pascal),
+                ("maxEntries".to_string(), Case::ScreamingSnake),
+                ("my_point".to_string(), Case::Pascal),
+                ("max_size".to_string(), Case::ScreamingSnake),
+                ("signed".to_string(), Case::Pascal),
+                ("signBook".to_string(), Case::Snake),
+            ],
+        );
+
+        let config = NamingConfig {
+            functions: None,
+            ..NamingConfig::default()
+        };
+        assert_eq!(check_src(src, &config).len(), 5);
+    }
+}
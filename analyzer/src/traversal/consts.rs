@@ -0,0 +1,131 @@
+use crate::errors::SemanticError;
+use crate::namespace::types::{
+    Base,
+    Type,
+};
+use fe_parser::ast as fe;
+use fe_parser::const_eval::{
+    eval_const,
+    ConstEvalError,
+    ConstValue,
+};
+use fe_parser::span::Spanned;
+use fe_parser::string_utils::decode_string;
+use std::collections::HashMap;
+
+/// Evaluate the condition of a static assertion and return an error holding
+/// the assertion's message if it does not hold.
+pub fn static_assert(
+    consts: &HashMap<String, ConstValue>,
+    cond: &Spanned<fe::Expr>,
+    msg: &Option<Spanned<Vec<&str>>>,
+) -> Result<(), SemanticError> {
+    match eval(consts, cond)? {
+        ConstValue::Bool(true) => Ok(()),
+        ConstValue::Bool(false) => {
+            let msg = msg.as_ref().map(|msg| decode_string(&msg.node));
+            Err(SemanticError::static_assertion_failed(msg).with_context(cond.span))
+        }
+        ConstValue::Int(_) => Err(SemanticError::type_error().with_context(cond.span)),
+    }
+}
+
+/// Check that a constant value can be stored in a value of the given type.
+pub fn check_type(value: &ConstValue, typ: &Type) -> Result<(), SemanticError> {
+    match (value, typ) {
+        (ConstValue::Int(val), Type::Base(Base::Numeric(integer))) => {
+            if integer.fits(&val.to_string()) {
+                Ok(())
+            } else {
+                Err(SemanticError::numeric_capacity_mismatch())
+            }
+        }
+        (ConstValue::Bool(_), Type::Base(Base::Bool)) => Ok(()),
+        _ => Err(SemanticError::type_error()),
+    }
+}
+
+/// Fold an expression made up of literals, names of constants in `consts`
+/// and operators into a value.
+pub fn eval(
+    consts: &HashMap<String, ConstValue>,
+    exp: &Spanned<fe::Expr>,
+) -> Result<ConstValue, SemanticError> {
+    eval_const(exp, consts).map_err(|error| {
+        match error {
+            ConstEvalError::DivisionByZero { .. }
+            | ConstEvalError::UnknownName { .. }
+            | ConstEvalError::NotConstant { .. } => SemanticError::non_const_expression(),
+            ConstEvalError::Overflow { .. } => SemanticError::numeric_capacity_mismatch(),
+            ConstEvalError::FloatLiteral { .. } => SemanticError::float_literal(),
+            ConstEvalError::TypeMismatch { .. } => SemanticError::type_error(),
+        }
+        .with_context(error.span())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ErrorKind;
+    use crate::traversal::consts::eval;
+    use fe_parser as parser;
+    use fe_parser::const_eval::ConstValue;
+    use num_bigint::BigInt;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    fn eval_src(src: &str) -> Result<ConstValue, ErrorKind> {
+        let tokens = parser::get_parse_tokens(src).expect("Couldn't parse expression");
+        let exp = parser::parsers::expr(&tokens[..])
+            .expect("Couldn't build expression AST")
+            .1;
+
+        let mut consts = HashMap::new();
+        consts.insert("SIZE".to_string(), ConstValue::Int(BigInt::from(32)));
+
+        eval(&consts, &exp).map_err(|error| error.kind)
+    }
+
+    #[rstest(
+        src,
+        expected,
+        case("comptime(2 ** 8)", 256),
+        case("1 + 2 * 3", 7),
+        case("0x10 - 0b1", 15),
+        case("-(7 // 2) % 3", 0),
+        case("1 << 4 | 1", 17),
+        case("0xff + 0b1", 256),
+        case("0o17 * 2", 30),
+        case("10 + 0x0a", 20),
+        case("0xff + 0b1 * 8", 263),
+        case("SIZE * 2", 64)
+    )]
+    fn fold_ints(src: &str, expected: i64) {
+        assert_eq!(eval_src(src), Ok(ConstValue::Int(BigInt::from(expected))))
+    }
+
+    #[rstest(
+        src,
+        expected,
+        case("not (1 < 2)", false),
+        case("2 ** 8 == 256 and true", true)
+    )]
+    fn fold_bools(src: &str, expected: bool) {
+        assert_eq!(eval_src(src), Ok(ConstValue::Bool(expected)))
+    }
+
+    #[rstest(
+        src,
+        expected,
+        case("comptime(x + 1)", ErrorKind::NonConstExpression),
+        case("2 ** 256", ErrorKind::NumericCapacityMismatch),
+        case("1e10", ErrorKind::FloatLiteral),
+        case("f()", ErrorKind::NonConstExpression),
+        case("1 / 0", ErrorKind::NonConstExpression),
+        case("1 + true", ErrorKind::TypeError),
+        case("not 1", ErrorKind::TypeError)
+    )]
+    fn fold_errors(src: &str, expected: ErrorKind) {
+        assert_eq!(eval_src(src), Err(expected))
+    }
+}
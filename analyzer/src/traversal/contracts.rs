@@ -12,7 +12,9 @@ use crate::namespace::types::{
     Type,
 };
 use crate::traversal::{
+    consts,
     functions,
+    structs,
     types,
 };
 use crate::{
@@ -30,7 +32,17 @@ pub fn contract_def(
     context: Shared<Context>,
     stmt: &Spanned<fe::ModuleStmt>,
 ) -> Result<(), SemanticError> {
-    if let fe::ModuleStmt::ContractDef { name, body } = &stmt.node {
+    if let fe::ModuleStmt::ContractDef {
+        name, base, body, ..
+    } = &stmt.node
+    {
+        if let Some(base) = base.first() {
+            return Err(
+                SemanticError::not_supported("contracts implementing interfaces")
+                    .with_context(base.span),
+            );
+        }
+
         let contract_scope = ContractScope::new(Rc::clone(&module_scope));
 
         for stmt in body.iter() {
@@ -42,6 +54,20 @@ pub fn contract_def(
                 fe::ContractStmt::FuncDef { .. } => {
                     functions::func_def(Rc::clone(&contract_scope), Rc::clone(&context), stmt)
                 }
+                // Struct types are only tracked at the module level, so a struct
+                // declared in a contract is added to the module's types.
+                fe::ContractStmt::StructDef { name, body, .. } => {
+                    structs::struct_def(Rc::clone(&module_scope), name.node, body)
+                }
+                fe::ContractStmt::Docstring { .. } => Ok(()),
+                fe::ContractStmt::StaticAssert { cond, msg } => {
+                    consts::static_assert(&module_scope.borrow().const_defs, cond, msg)
+                }
+                // Packing is not implemented yet, so grouped fields get their
+                // own slots like any other field.
+                fe::ContractStmt::PackedGroup { fields } => fields
+                    .iter()
+                    .try_for_each(|field| contract_field(Rc::clone(&contract_scope), field)),
             }
             .map_err(|error| error.with_context(stmt.span))?;
         }
@@ -91,7 +117,13 @@ fn event_def(
     scope: Shared<ContractScope>,
     stmt: &Spanned<fe::ContractStmt>,
 ) -> Result<(), SemanticError> {
-    if let fe::ContractStmt::EventDef { name, fields } = &stmt.node {
+    if let fe::ContractStmt::EventDef {
+        name,
+        fields,
+        anonymous,
+        ..
+    } = &stmt.node
+    {
         let name = name.node;
 
         let (is_indexed_bools, fields): (Vec<bool>, Vec<FixedSize>) = fields
@@ -108,7 +140,10 @@ fn event_def(
             .map(|(index, _)| index)
             .collect::<Vec<_>>();
 
-        if indexed_fields.len() > 3 {
+        // anonymous events do not use a topic for their signature, which leaves
+        // room for a fourth indexed field
+        let max_indexed_fields = if *anonymous { 4 } else { 3 };
+        if indexed_fields.len() > max_indexed_fields {
             return Err(SemanticError::more_than_three_indexed_params());
         }
 
@@ -122,7 +157,7 @@ fn event_def(
 
         return scope
             .borrow_mut()
-            .add_event(name, Event::new(name, fields, indexed_fields));
+            .add_event(name, Event::new(name, fields, indexed_fields, *anonymous));
     }
 
     unreachable!()
@@ -4,6 +4,7 @@ use crate::namespace::operations;
 use crate::namespace::scopes::{
     BlockScope,
     ContractFunctionDef,
+    Scope,
     Shared,
 };
 use crate::namespace::types::{
@@ -23,6 +24,10 @@ use crate::traversal::_utils::{
     fixed_sizes_to_types,
     spanned_expression,
 };
+use crate::traversal::{
+    consts,
+    types,
+};
 use crate::{
     CallType,
     Context,
@@ -41,6 +46,11 @@ use builtins::{
     TxField,
 };
 use fe_parser::ast as fe;
+use fe_parser::const_eval::ConstValue;
+use fe_parser::numeric::{
+    classify,
+    NumClass,
+};
 use fe_parser::span::Spanned;
 use std::convert::TryFrom;
 use std::rc::Rc;
@@ -54,7 +64,7 @@ pub fn expr(
 ) -> Result<ExpressionAttributes, SemanticError> {
     let attributes = match &exp.node {
         fe::Expr::Name(_) => expr_name(scope, exp),
-        fe::Expr::Num(_) => expr_num(exp),
+        fe::Expr::Num { .. } => expr_num(exp),
         fe::Expr::Bool(_) => expr_bool(exp),
         fe::Expr::Subscript { .. } => expr_subscript(scope, Rc::clone(&context), exp),
         fe::Expr::Attribute { .. } => expr_attribute(scope, Rc::clone(&context), exp),
@@ -66,9 +76,14 @@ pub fn expr(
         fe::Expr::Call { .. } => expr_call(scope, Rc::clone(&context), exp),
         fe::Expr::List { .. } => expr_list(scope, Rc::clone(&context), exp),
         fe::Expr::ListComp { .. } => unimplemented!(),
+        fe::Expr::AnnotatedList { .. } => expr_annotated_list(scope, Rc::clone(&context), exp),
+        fe::Expr::ArrayRepeat { .. } => expr_array_repeat(scope, Rc::clone(&context), exp),
+        fe::Expr::Cast { .. } => Err(SemanticError::not_supported("cast expressions")),
+        fe::Expr::Comptime { .. } => expr_comptime(scope, Rc::clone(&context), exp),
         fe::Expr::Tuple { .. } => expr_tuple(scope, Rc::clone(&context), exp),
         fe::Expr::Str(_) => expr_str(scope, exp),
         fe::Expr::Ellipsis => unimplemented!(),
+        fe::Expr::OperatorRef(_) => Err(SemanticError::not_supported("operator references")),
     }
     .map_err(|error| error.with_context(exp.span))?;
 
@@ -115,6 +130,89 @@ pub fn expr_list(
     unreachable!()
 }
 
+/// Check that every element of an array literal with an element type
+/// annotation fits the annotated element type.
+///
+/// Such literals are rejected once checked, since no code can be generated for
+/// them yet.
+pub fn expr_annotated_list(
+    scope: Shared<BlockScope>,
+    context: Shared<Context>,
+    exp: &Spanned<fe::Expr>,
+) -> Result<ExpressionAttributes, SemanticError> {
+    if let fe::Expr::AnnotatedList { elts, typ } = &exp.node {
+        let array = match types::type_desc(Scope::Block(Rc::clone(&scope)), typ)? {
+            Type::Array(array) if array.size == elts.len() => array,
+            _ => return Err(SemanticError::type_error()),
+        };
+        let inner = Type::Base(array.inner.clone());
+
+        for elt in elts.iter() {
+            let attributes = expr(Rc::clone(&scope), Rc::clone(&context), elt)?;
+
+            if let fe::Expr::Num { value: num, .. } = &elt.node {
+                validate_numeric_literal_fits_type(num, &inner)
+                    .map_err(|error| error.with_context(elt.span))?;
+            } else if attributes.typ != inner {
+                return Err(SemanticError::type_error().with_context(elt.span));
+            }
+        }
+
+        return Err(SemanticError::not_supported(
+            "array literals with an element type annotation",
+        ));
+    }
+
+    unreachable!()
+}
+
+/// Check an array literal with a repeated value, whose length must be a
+/// constant.
+///
+/// Such literals are rejected once checked, since no code can be generated for
+/// them yet.
+pub fn expr_array_repeat(
+    scope: Shared<BlockScope>,
+    context: Shared<Context>,
+    exp: &Spanned<fe::Expr>,
+) -> Result<ExpressionAttributes, SemanticError> {
+    if let fe::Expr::ArrayRepeat { value, count } = &exp.node {
+        let module_scope = scope.borrow().module_scope();
+        let count_value = consts::eval(&module_scope.borrow().const_defs, count)?;
+        match count_value {
+            ConstValue::Int(len) if usize::try_from(&len).is_ok() => {}
+            _ => return Err(SemanticError::type_error().with_context(count.span)),
+        }
+
+        if let Type::Base(_) = expr(scope, context, value)?.typ {
+            return Err(SemanticError::not_supported(
+                "array literals with a repeated value",
+            ));
+        }
+
+        return Err(SemanticError::type_error().with_context(value.span));
+    }
+
+    unreachable!()
+}
+
+/// Gather context information for a comptime expression and check that its
+/// inner expression can be evaluated at compile time.
+pub fn expr_comptime(
+    scope: Shared<BlockScope>,
+    context: Shared<Context>,
+    exp: &Spanned<fe::Expr>,
+) -> Result<ExpressionAttributes, SemanticError> {
+    if let fe::Expr::Comptime { inner } = &exp.node {
+        let module_scope = scope.borrow().module_scope();
+        consts::eval(&module_scope.borrow().const_defs, inner)?;
+
+        return expr(scope, context, inner);
+    }
+
+    unreachable!()
+}
+
 /// Gather context information for a tuple expression and check for type errors.
 pub fn expr_tuple(
     _scope: Shared<BlockScope>,
@@ -271,7 +369,7 @@ fn expr_bool(exp: &Spanned<fe::Expr>) -> Result<ExpressionAttributes, SemanticEr
 }
 
 fn expr_num(exp: &Spanned<fe::Expr>) -> Result<ExpressionAttributes, SemanticError> {
-    if let fe::Expr::Num(num) = &exp.node {
+    if let fe::Expr::Num { value: num, .. } = &exp.node {
         validate_numeric_literal_fits_type(*num, &Type::Base(U256))?;
         return Ok(ExpressionAttributes::new(Type::Base(U256), Location::Value));
     }
@@ -486,7 +584,16 @@ fn expr_call(
     context: Shared<Context>,
     exp: &Spanned<fe::Expr>,
 ) -> Result<ExpressionAttributes, SemanticError> {
-    if let fe::Expr::Call { func, args } = &exp.node {
+    if let fe::Expr::Call {
+        func,
+        call_modifiers,
+        args,
+    } = &exp.node
+    {
+        if let Some(modifier) = call_modifiers.first() {
+            return Err(SemanticError::not_supported("call modifiers").with_context(modifier.span));
+        }
+
         return match expr_call_type(Rc::clone(&scope), Rc::clone(&context), func)? {
             CallType::BuiltinFunction { func } => {
                 expr_call_builtin_function(scope, context, func, args)
@@ -926,10 +1033,10 @@ fn validate_are_kw_args(args: &[Spanned<fe::CallArg>]) -> Result<(), SemanticErr
 
 fn validate_is_numeric_literal(call_arg: &fe::CallArg) -> Result<String, SemanticError> {
     if let fe::CallArg::Arg(fe::Expr::UnaryOperation { operand, op: _ }) = call_arg {
-        if let fe::Expr::Num(num) = (*operand).node {
+        if let fe::Expr::Num { value: num, .. } = (*operand).node {
             return Ok(format!("-{}", num));
         }
-    } else if let fe::CallArg::Arg(fe::Expr::Num(num)) = call_arg {
+    } else if let fe::CallArg::Arg(fe::Expr::Num { value: num, .. }) = call_arg {
         return Ok(num.to_string());
     }
 
@@ -937,6 +1044,10 @@ fn validate_is_numeric_literal(call_arg: &fe::CallArg) -> Result<String, Semanti
 }
 
 fn validate_numeric_literal_fits_type(num: &str, typ: &Type) -> Result<(), SemanticError> {
+    if classify(num) == NumClass::Float {
+        return Err(SemanticError::float_literal());
+    }
+
     if let Type::Base(Base::Numeric(integer)) = typ {
         if integer.fits(num) {
             return Ok(());
@@ -41,13 +41,32 @@ pub fn func_def(
     def: &Spanned<fe::ContractStmt>,
 ) -> Result<(), SemanticError> {
     if let fe::ContractStmt::FuncDef {
+        decorators,
         qual,
         name,
+        generic_params,
         args,
         return_type,
+        named_returns,
         body: _,
+        is_constructor: _,
+        init_only: _,
     } = &def.node
     {
+        if let Some(decorator) = decorators.first() {
+            return Err(SemanticError::not_supported("decorators").with_context(decorator.span));
+        }
+
+        if let Some(named_return) = named_returns.first() {
+            return Err(
+                SemanticError::not_supported("named return values").with_context(named_return.span)
+            );
+        }
+
+        if let Some(param) = generic_params.first() {
+            return Err(SemanticError::not_supported("generic functions").with_context(param.span));
+        }
+
         let name = name.node;
         let function_scope = BlockScope::from_contract_scope(name, Rc::clone(&contract_scope));
 
@@ -92,11 +111,16 @@ pub fn func_body(
     def: &Spanned<fe::ContractStmt>,
 ) -> Result<(), SemanticError> {
     if let fe::ContractStmt::FuncDef {
+        decorators: _,
         qual: _,
         name,
+        generic_params: _,
         args: _,
         return_type: _,
+        named_returns: _,
         body,
+        is_constructor: _,
+        init_only: _,
     } = &def.node
     {
         let host_func_def = contract_scope
@@ -188,6 +212,8 @@ fn func_stmt(
         fe::FuncStmt::For { .. } => for_loop(scope, context, stmt),
         fe::FuncStmt::While { .. } => while_loop(scope, context, stmt),
         fe::FuncStmt::If { .. } => if_statement(scope, context, stmt),
+        fe::FuncStmt::Unchecked { .. } => unchecked(scope, context, stmt),
+        fe::FuncStmt::Yul { .. } => Err(SemanticError::not_supported("inline Yul blocks")),
         fe::FuncStmt::Assert { .. } => assert(scope, context, stmt),
         fe::FuncStmt::Expr { .. } => expr(scope, context, stmt),
         fe::FuncStmt::Pass => Ok(()),
@@ -310,6 +336,23 @@ fn if_statement(
     }
 }
 
+fn unchecked(
+    scope: Shared<BlockScope>,
+    context: Shared<Context>,
+    stmt: &Spanned<fe::FuncStmt>,
+) -> Result<(), SemanticError> {
+    if let fe::FuncStmt::Unchecked { body } = &stmt.node {
+        let body_scope = BlockScope::from_block_scope(BlockScopeType::Unchecked, scope);
+        traverse_statements(body_scope, context, body)?;
+
+        // The body is checked first so that its errors are reported, but no
+        // code without overflow checks can be generated yet.
+        return Err(SemanticError::not_supported("unchecked blocks"));
+    }
+
+    unreachable!()
+}
+
 fn while_loop(
     scope: Shared<BlockScope>,
     context: Shared<Context>,
@@ -352,7 +395,7 @@ fn emit(
 ) -> Result<(), SemanticError> {
     if let fe::FuncStmt::Emit {
         value: Spanned {
-            node: fe::Expr::Call { func, args },
+            node: fe::Expr::Call { func, args, .. },
             ..
         },
     } = &stmt.node
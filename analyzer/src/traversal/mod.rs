@@ -1,5 +1,6 @@
 mod _utils;
 mod assignments;
+mod consts;
 mod contracts;
 mod declarations;
 mod expressions;
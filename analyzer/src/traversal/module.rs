@@ -5,6 +5,7 @@ use crate::namespace::scopes::{
 };
 use crate::namespace::types;
 use crate::traversal::{
+    consts,
     contracts,
     structs,
 };
@@ -20,14 +21,25 @@ pub fn module(context: Shared<Context>, module: &fe::Module) -> Result<(), Seman
     for stmt in module.body.iter() {
         match &stmt.node {
             fe::ModuleStmt::TypeDef { .. } => type_def(Rc::clone(&scope), stmt)?,
-            fe::ModuleStmt::StructDef { name, body } => {
+            fe::ModuleStmt::ConstDef { .. } => const_def(Rc::clone(&scope), stmt)?,
+            fe::ModuleStmt::StructDef { name, body, .. } => {
                 structs::struct_def(Rc::clone(&scope), name.node, body)?
             }
             fe::ModuleStmt::ContractDef { .. } => {
                 contracts::contract_def(Rc::clone(&scope), Rc::clone(&context), stmt)?
             }
+            fe::ModuleStmt::InterfaceDef { .. } => {
+                return Err(SemanticError::not_supported("interfaces").with_context(stmt.span))
+            }
             fe::ModuleStmt::FromImport { .. } => unimplemented!(),
             fe::ModuleStmt::SimpleImport { .. } => unimplemented!(),
+            fe::ModuleStmt::Export { .. } => {
+                return Err(SemanticError::not_supported("exports").with_context(stmt.span))
+            }
+            fe::ModuleStmt::Docstring { .. } => {}
+            fe::ModuleStmt::StaticAssert { cond, msg } => {
+                consts::static_assert(&scope.borrow().const_defs, cond, msg)?
+            }
         }
     }
 
@@ -46,3 +58,18 @@ fn type_def(
 
     unreachable!()
 }
+
+fn const_def(
+    scope: Shared<ModuleScope>,
+    def: &Spanned<fe::ModuleStmt>,
+) -> Result<(), SemanticError> {
+    if let fe::ModuleStmt::ConstDef { name, typ, value } = &def.node {
+        let typ = types::type_desc(&scope.borrow().type_defs, &typ.node)?;
+        let value = consts::eval(&scope.borrow().const_defs, value)?;
+        consts::check_type(&value, &typ).map_err(|error| error.with_context(def.span))?;
+        scope.borrow_mut().add_const_def(name.node, value)?;
+        return Ok(());
+    }
+
+    unreachable!()
+}
@@ -1,4 +1,8 @@
 use fe_analyzer;
+use fe_analyzer::errors::{
+    ErrorKind,
+    SemanticError,
+};
 use fe_analyzer::namespace::types::{
     Array,
     Base,
@@ -116,3 +120,258 @@ fn guest_book_analysis() {
         "0xf95318ba442251854c1277ed370e2adb2cd6dc2156bdffdd75dc5d798b31ab0f"
     );
 }
+
+fn analyze_src(src: &str) -> Result<(), SemanticError> {
+    let tokens = fe_parser::get_parse_tokens(src).expect("couldn't tokenize source");
+    let fe_module = fe_parser::parsers::file_input(&tokens[..])
+        .expect("failed to parse source")
+        .1
+        .node;
+
+    fe_analyzer::analyze(&fe_module).map(|_| ())
+}
+
+#[test]
+fn static_assert_analysis() {
+    assert_eq!(
+        analyze_src("static_assert(2 ** 8 == 256, \"bad size\")\n"),
+        Ok(())
+    );
+    assert_eq!(
+        analyze_src("contract Foo:\n    static_assert(-1 < 0 and not false)\n"),
+        Ok(())
+    );
+
+    let error = analyze_src("static_assert(1 + 1 > 2, \"too big\")\n").unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::StaticAssertionFailed(Some("too big".to_string()))
+    );
+    assert_eq!(error.context, vec![Span::new(14, 23)]);
+
+    let error = analyze_src("contract Foo:\n    static_assert(false)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::StaticAssertionFailed(None));
+
+    let error = analyze_src("static_assert(SIZE <= 32)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NonConstExpression);
+    assert_eq!(error.context, vec![Span::new(14, 18)]);
+
+    assert_eq!(
+        analyze_src(
+            "const SIZE: u256 = 16\ncontract Foo:\n    static_assert(SIZE <= 32, \"too big\")\n"
+        ),
+        Ok(())
+    );
+
+    let error = analyze_src("static_assert(2 ** 256 > 0)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NumericCapacityMismatch);
+}
+
+#[test]
+fn const_def_analysis() {
+    let src =
+        "const SIZE: u256 = 2 ** 5\nconst DOUBLE: u8 = SIZE * 2\nstatic_assert(DOUBLE == 64)\n";
+    assert_eq!(analyze_src(src), Ok(()));
+
+    let error = analyze_src("const SIZE: u8 = 256\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NumericCapacityMismatch);
+    assert_eq!(error.context, vec![Span::new(0, 20)]);
+
+    let error = analyze_src("const FLAG: bool = 1\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::TypeError);
+
+    let error = analyze_src("const SIZE: u8 = 1\nconst SIZE: u8 = 2\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::AlreadyDefined);
+
+    let error = analyze_src("const SIZE: u8 = LEN\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NonConstExpression);
+    assert_eq!(error.context, vec![Span::new(17, 20)]);
+}
+
+#[test]
+fn comptime_analysis() {
+    let src = "contract Foo:\n    pub def bar() -> u256:\n        return comptime(2 ** 8)\n";
+    assert_eq!(analyze_src(src), Ok(()));
+
+    let src = "contract Foo:\n    pub def bar(x: u256) -> u256:\n        return comptime(x)\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NonConstExpression);
+    assert_eq!(error.context[0], Span::new(72, 73));
+}
+
+#[test]
+fn array_repeat_analysis() {
+    let src = "const SIZE: u256 = 4\ncontract Foo:\n    pub def bar(n: u256):\n        x: u256[4] = [0; SIZE]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::NotSupported("array literals with a repeated value")
+    );
+
+    let src = "contract Foo:\n    pub def bar(n: u256):\n        x: u256[4] = [0; n]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NonConstExpression);
+    assert_eq!(error.context[0], Span::new(65, 66));
+
+    let src = "contract Foo:\n    pub def bar():\n        x: u256[4] = [0; -1]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::TypeError);
+}
+
+#[test]
+fn unchecked_analysis() {
+    let src = "contract Foo:\n    pub def bar(x: u8):\n        unchecked:\n            y: u8 = x\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("unchecked blocks"));
+    assert_eq!(error.context[0], Span::new(46, 78));
+
+    let src = "contract Foo:\n    pub def bar(x: u8):\n        unchecked:\n            x = y\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UndefinedValue);
+
+    let src = "contract Foo:\n    pub def bar():\n        yul:\n            sstore(0, 1)\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("inline Yul blocks"));
+}
+
+#[test]
+fn call_modifiers_analysis() {
+    let src =
+        "contract Foo:\n    pub def bar(addr: address):\n        Foo(addr).bar{value: 1}(addr)\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("call modifiers"));
+    assert_eq!(error.context[0], Span::new(68, 76));
+}
+
+#[test]
+fn generic_function_analysis() {
+    let src =
+        "contract Foo:\n    pub def sum<const N: u256>(a: u256[N]) -> u256:\n        return a[0]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("generic functions"));
+    assert_eq!(error.context[0], Span::new(30, 43));
+}
+
+#[test]
+fn never_type_analysis() {
+    let src = "contract Foo:\n    pub def bar() -> !:\n        revert\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("the never type"));
+}
+
+#[test]
+fn int_type_analysis() {
+    let src = "contract Foo:\n    pub def bar(x: uint<64>) -> u64:\n        return x\n";
+    assert_eq!(analyze_src(src), Ok(()));
+
+    let src = "contract Foo:\n    pub def bar(x: uint<24>):\n        pass\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::NotSupported("integer widths other than 8, 16, 32, 64, 128 and 256 bits")
+    );
+}
+
+#[test]
+fn operator_ref_analysis() {
+    let src = "contract Foo:\n    pub def bar():\n        x: u256 = (+)\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("operator references"));
+    assert_eq!(error.context[0], Span::new(51, 54));
+}
+
+#[test]
+fn cast_analysis() {
+    let src = "contract Foo:\n    pub def bar(x: u256) -> u8:\n        return x as! u8\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("cast expressions"));
+    assert_eq!(error.context[0], Span::new(61, 69));
+}
+
+#[test]
+fn contract_base_analysis() {
+    let src = "contract Foo(Bar, Baz):\n    x: u256\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::NotSupported("contracts implementing interfaces")
+    );
+    assert_eq!(error.context[0], Span::new(13, 16));
+}
+
+#[test]
+fn interface_analysis() {
+    let src = "interface Token:\n    def total_supply() -> u256\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("interfaces"));
+    assert_eq!(error.context[0], Span::new(0, 47));
+}
+
+#[test]
+fn export_analysis() {
+    let src = "export foo.Bar as Baz\ncontract Foo:\n    x: u256\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("exports"));
+    assert_eq!(error.context[0], Span::new(0, 21));
+}
+
+#[test]
+fn named_returns_analysis() {
+    let src = "contract Foo:\n    pub def bar() -> (total: u256, ok: bool):\n        pass\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("named return values"));
+    assert_eq!(error.context[0], Span::new(36, 47));
+}
+
+#[test]
+fn decorator_analysis() {
+    let src = "contract Foo:\n    @payable\n    pub def bar():\n        pass\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotSupported("decorators"));
+    assert_eq!(error.context[0], Span::new(18, 26));
+}
+
+#[test]
+fn annotated_list_analysis() {
+    let src = "contract Foo:\n    pub def bar():\n        x: u8[3] = [1, 2, 255]: u8[3]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::NotSupported("array literals with an element type annotation")
+    );
+    assert_eq!(error.context[0], Span::new(52, 70));
+
+    let src = "contract Foo:\n    pub def bar():\n        x: u8[3] = [1, 256, 3]: u8[3]\n";
+    let error = analyze_src(src).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NumericCapacityMismatch);
+    assert_eq!(error.context[0], Span::new(56, 59));
+}
+
+#[test]
+fn anonymous_event_indexed_fields() {
+    let fields = "        idx a: address\n        idx b: address\n        idx c: u256\n        idx d: bool\n";
+
+    let src = format!("contract Foo:\n    event Bar anonymous:\n{}", fields);
+    assert_eq!(analyze_src(&src), Ok(()));
+
+    let src = format!("contract Foo:\n    event Bar:\n{}", fields);
+    assert_eq!(
+        analyze_src(&src).unwrap_err().kind,
+        ErrorKind::MoreThanThreeIndexedParams
+    );
+}
+
+#[test]
+fn contract_struct_def() {
+    let src = "\
+contract Foo:
+    struct Point:
+        x: u256
+        y: u256
+
+    pub def origin() -> u256:
+        p: Point = Point(x=0, y=0)
+        return p.x
+";
+    assert_eq!(analyze_src(src), Ok(()));
+}
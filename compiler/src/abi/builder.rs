@@ -30,7 +30,7 @@ pub fn module<'a>(module: &'a fe::Module<'a>) -> Result<ModuleAbis, CompileError
                         return Err(CompileError::static_str("duplicate type definition"));
                     }
                 }
-                fe::ModuleStmt::ContractDef { name, body } => {
+                fe::ModuleStmt::ContractDef { name, body, .. } => {
                     if abis
                         .insert(name.node.to_string(), contract_def(&type_defs, body)?)
                         .is_some()
@@ -65,10 +65,19 @@ fn contract_def<'a>(
                     }
                 }
             }
-            fe::ContractStmt::EventDef { name, fields } => {
-                c.events.push(event_def(type_defs, name.node, fields)?)
-            }
-            fe::ContractStmt::ContractField { .. } => {}
+            fe::ContractStmt::EventDef {
+                name,
+                fields,
+                anonymous,
+                ..
+            } => c
+                .events
+                .push(event_def(type_defs, name.node, fields, *anonymous)?),
+            fe::ContractStmt::ContractField { .. }
+            | fe::ContractStmt::Docstring { .. }
+            | fe::ContractStmt::StaticAssert { .. }
+            | fe::ContractStmt::PackedGroup { .. }
+            | fe::ContractStmt::StructDef { .. } => {}
         }
 
         Ok(c)
@@ -79,6 +88,7 @@ fn event_def<'a>(
     type_defs: &'a TypeDefs<'a>,
     name: &str,
     fields: &[Spanned<fe::EventField<'a>>],
+    anonymous: bool,
 ) -> Result<Event, CompileError> {
     let fields = fields
         .iter()
@@ -89,7 +99,7 @@ fn event_def<'a>(
         name: name.to_owned(),
         typ: "event".to_owned(),
         fields,
-        anonymous: false,
+        anonymous,
     })
 }
 
@@ -189,7 +199,29 @@ fn type_desc<'a>(
             let inner = type_desc(type_defs, &typ.node)?;
             Ok(VarType::FixedArray(Box::new(inner), *dimension))
         }
+        fe::TypeDesc::Int { signed, bits } => match (signed, bits) {
+            (false, 256) => Ok(VarType::Uint256),
+            (false, 128) => Ok(VarType::Uint128),
+            (false, 64) => Ok(VarType::Uint64),
+            (false, 32) => Ok(VarType::Uint32),
+            (false, 16) => Ok(VarType::Uint16),
+            (false, 8) => Ok(VarType::Uint8),
+            (true, 256) => Ok(VarType::Int256),
+            (true, 128) => Ok(VarType::Int128),
+            (true, 64) => Ok(VarType::Int64),
+            (true, 32) => Ok(VarType::Int32),
+            (true, 16) => Ok(VarType::Int16),
+            (true, 8) => Ok(VarType::Int8),
+            _ => Err(CompileError::str(&format!(
+                "integer width not supported in ABI: {}",
+                bits
+            ))),
+        },
+        fe::TypeDesc::GenericArray { .. } => Err(CompileError::static_str(
+            "generic arrays not supported in ABI",
+        )),
         fe::TypeDesc::Map { .. } => Err(CompileError::static_str("maps not supported in ABI")),
+        fe::TypeDesc::Never => Err(CompileError::static_str("never type not supported in ABI")),
         fe::TypeDesc::Tuple { items } => {
             let items = items
                 .iter()
@@ -0,0 +1,75 @@
+//! Diagnostics emitted while compiling.
+
+use crate::errors::ErrorKind;
+use fe_parser::keywords::ReservedWordWarning;
+use fe_parser::numeric::HexGroupingWarning;
+use fe_parser::span::Span;
+
+/// The severity of a diagnostic.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A message about the source code being compiled.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The span of the diagnostic's origin in the source code, if known.
+    pub span: Option<Span>,
+}
+
+impl From<&ErrorKind> for Diagnostic {
+    fn from(error: &ErrorKind) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: error.to_string(),
+            span: error.span(),
+        }
+    }
+}
+
+impl From<&ReservedWordWarning<'_>> for Diagnostic {
+    fn from(warning: &ReservedWordWarning) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: warning.message(),
+            span: Some(warning.span),
+        }
+    }
+}
+
+impl From<&HexGroupingWarning<'_>> for Diagnostic {
+    fn from(warning: &HexGroupingWarning) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: warning.message(),
+            span: Some(warning.span),
+        }
+    }
+}
+
+/// Receives diagnostics as soon as the compiler finds them.
+pub trait DiagnosticSink {
+    fn emit(&mut self, diag: Diagnostic);
+}
+
+/// A sink that collects diagnostics in the order in which they were emitted.
+#[derive(Debug, Default)]
+pub struct VecSink {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl VecSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DiagnosticSink for VecSink {
+    fn emit(&mut self, diag: Diagnostic) {
+        self.diagnostics.push(diag);
+    }
+}
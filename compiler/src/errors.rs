@@ -1,19 +1,154 @@
 //! Errors returned by the compilers and ABI builder.
 
+use fe_analyzer::errors::{
+    ErrorKind as SemanticErrorKind,
+    SemanticError,
+};
+use fe_parser::errors::Suggestion;
+use fe_parser::span::Span;
 use fe_parser::tokenizer::TokenizeError;
 use serde::export::Formatter;
 
-/// Errors can either be an object or static reference.
-#[derive(Debug)]
+/// Errors can either be an object or static reference, or a semantic error
+/// found while analyzing the source code.
+///
+/// Semantic errors carry the span of their origin in the source code and a
+/// message formatted for the user.  If the analyzer did not record where the
+/// error occurred, the span is empty and starts at offset 0.
+#[derive(Debug, PartialEq)]
 pub enum ErrorKind {
     StaticStr(&'static str),
     Str(String),
+    BreakWithoutLoop { span: Span, message: String },
+    ContinueWithoutLoop { span: Span, message: String },
+    KeyWordArgsRequired { span: Span, message: String },
+    MissingReturn { span: Span, message: String },
+    NotSubscriptable { span: Span, message: String },
+    NumericCapacityMismatch { span: Span, message: String },
+    StringCapacityMismatch { span: Span, message: String },
+    UndefinedValue { span: Span, message: String },
+    UnexpectedReturn { span: Span, message: String },
+    TypeError { span: Span, message: String },
+    CannotMove { span: Span, message: String },
+    NotCallable { span: Span, message: String },
+    NumericLiteralExpected { span: Span, message: String },
+    MoreThanThreeIndexedParams { span: Span, message: String },
+    WrongNumberOfParams { span: Span, message: String },
+    AlreadyDefined { span: Span, message: String },
+    FloatLiteral { span: Span, message: String },
+    StaticAssertionFailed { span: Span, message: String },
+    NonConstExpression { span: Span, message: String },
+    NotSupported { span: Span, message: String },
 }
 
 /// List of errors encountered during compilation.
 #[derive(Debug)]
 pub struct CompileError {
     pub errors: Vec<ErrorKind>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl ErrorKind {
+    /// Create an error kind from a semantic error, using `src` to format the
+    /// error message.
+    pub fn semantic(error: &SemanticError, src: &str) -> Self {
+        let span = error
+            .context
+            .first()
+            .copied()
+            .unwrap_or_else(|| Span::new(0, 0));
+        let message = error.format_user(src);
+
+        match error.kind {
+            SemanticErrorKind::BreakWithoutLoop => Self::BreakWithoutLoop { span, message },
+            SemanticErrorKind::ContinueWithoutLoop => Self::ContinueWithoutLoop { span, message },
+            SemanticErrorKind::KeyWordArgsRequired => Self::KeyWordArgsRequired { span, message },
+            SemanticErrorKind::MissingReturn => Self::MissingReturn { span, message },
+            SemanticErrorKind::NotSubscriptable => Self::NotSubscriptable { span, message },
+            SemanticErrorKind::NumericCapacityMismatch => {
+                Self::NumericCapacityMismatch { span, message }
+            }
+            SemanticErrorKind::StringCapacityMismatch => {
+                Self::StringCapacityMismatch { span, message }
+            }
+            SemanticErrorKind::UndefinedValue => Self::UndefinedValue { span, message },
+            SemanticErrorKind::UnexpectedReturn => Self::UnexpectedReturn { span, message },
+            SemanticErrorKind::TypeError => Self::TypeError { span, message },
+            SemanticErrorKind::CannotMove => Self::CannotMove { span, message },
+            SemanticErrorKind::NotCallable => Self::NotCallable { span, message },
+            SemanticErrorKind::NumericLiteralExpected => {
+                Self::NumericLiteralExpected { span, message }
+            }
+            SemanticErrorKind::MoreThanThreeIndexedParams => {
+                Self::MoreThanThreeIndexedParams { span, message }
+            }
+            SemanticErrorKind::WrongNumberOfParams => Self::WrongNumberOfParams { span, message },
+            SemanticErrorKind::AlreadyDefined => Self::AlreadyDefined { span, message },
+            SemanticErrorKind::FloatLiteral => Self::FloatLiteral { span, message },
+            SemanticErrorKind::StaticAssertionFailed(_) => {
+                Self::StaticAssertionFailed { span, message }
+            }
+            SemanticErrorKind::NonConstExpression => Self::NonConstExpression { span, message },
+            SemanticErrorKind::NotSupported(_) => Self::NotSupported { span, message },
+        }
+    }
+
+    /// A stable numeric code identifying the kind of error.
+    ///
+    /// Errors that are not semantic errors share the code 0.  Codes of
+    /// semantic errors must never be changed or reused.
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::StaticStr(_) | Self::Str(_) => 0,
+            Self::BreakWithoutLoop { .. } => 1,
+            Self::ContinueWithoutLoop { .. } => 2,
+            Self::KeyWordArgsRequired { .. } => 3,
+            Self::MissingReturn { .. } => 4,
+            Self::NotSubscriptable { .. } => 5,
+            Self::NumericCapacityMismatch { .. } => 6,
+            Self::StringCapacityMismatch { .. } => 7,
+            Self::UndefinedValue { .. } => 8,
+            Self::UnexpectedReturn { .. } => 9,
+            Self::TypeError { .. } => 10,
+            Self::CannotMove { .. } => 11,
+            Self::NotCallable { .. } => 12,
+            Self::NumericLiteralExpected { .. } => 13,
+            Self::MoreThanThreeIndexedParams { .. } => 14,
+            Self::WrongNumberOfParams { .. } => 15,
+            Self::AlreadyDefined { .. } => 16,
+            Self::FloatLiteral { .. } => 17,
+            Self::StaticAssertionFailed { .. } => 18,
+            Self::NonConstExpression { .. } => 19,
+            Self::NotSupported { .. } => 20,
+        }
+    }
+
+    /// The span of the error's origin in the source code, if known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::StaticStr(_) | Self::Str(_) => None,
+            Self::BreakWithoutLoop { span, .. }
+            | Self::ContinueWithoutLoop { span, .. }
+            | Self::KeyWordArgsRequired { span, .. }
+            | Self::MissingReturn { span, .. }
+            | Self::NotSubscriptable { span, .. }
+            | Self::NumericCapacityMismatch { span, .. }
+            | Self::StringCapacityMismatch { span, .. }
+            | Self::UndefinedValue { span, .. }
+            | Self::UnexpectedReturn { span, .. }
+            | Self::TypeError { span, .. }
+            | Self::CannotMove { span, .. }
+            | Self::NotCallable { span, .. }
+            | Self::NumericLiteralExpected { span, .. }
+            | Self::MoreThanThreeIndexedParams { span, .. }
+            | Self::WrongNumberOfParams { span, .. }
+            | Self::AlreadyDefined { span, .. }
+            | Self::FloatLiteral { span, .. }
+            | Self::StaticAssertionFailed { span, .. }
+            | Self::NonConstExpression { span, .. }
+            | Self::NotSupported { span, .. } => Some(*span),
+        }
+    }
 }
 
 impl std::fmt::Display for ErrorKind {
@@ -21,6 +156,28 @@ impl std::fmt::Display for ErrorKind {
         match self {
             Self::StaticStr(s) => write!(f, "{}", s),
             Self::Str(s) => write!(f, "{}", s),
+            Self::BreakWithoutLoop { message, .. }
+            | Self::ContinueWithoutLoop { message, .. }
+            | Self::KeyWordArgsRequired { message, .. }
+            | Self::MissingReturn { message, .. }
+            | Self::NotSubscriptable { message, .. }
+            | Self::NumericCapacityMismatch { message, .. }
+            | Self::StringCapacityMismatch { message, .. }
+            | Self::UndefinedValue { message, .. }
+            | Self::UnexpectedReturn { message, .. }
+            | Self::TypeError { message, .. }
+            | Self::CannotMove { message, .. }
+            | Self::NotCallable { message, .. }
+            | Self::NumericLiteralExpected { message, .. }
+            | Self::MoreThanThreeIndexedParams { message, .. }
+            | Self::WrongNumberOfParams { message, .. }
+            | Self::AlreadyDefined { message, .. }
+            | Self::FloatLiteral { message, .. }
+            | Self::StaticAssertionFailed { message, .. }
+            | Self::NonConstExpression { message, .. }
+            | Self::NotSupported { message, .. } => {
+                write!(f, "error[E{:04}]: {}", self.code(), message)
+            }
         }
     }
 }
@@ -43,13 +200,17 @@ impl std::fmt::Display for CompileError {
 
 impl CompileError {
     pub fn new() -> Self {
-        Self { errors: Vec::new() }
+        Self {
+            errors: Vec::new(),
+            suggestions: Vec::new(),
+        }
     }
 
     /// Create a single error with a static string.
     pub fn static_str(val: &'static str) -> Self {
         Self {
             errors: vec![ErrorKind::StaticStr(val)],
+            suggestions: Vec::new(),
         }
     }
 
@@ -57,8 +218,28 @@ impl CompileError {
     pub fn str(val: &str) -> Self {
         Self {
             errors: vec![ErrorKind::Str(val.to_owned())],
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Create a single error from a semantic error.
+    pub fn semantic(error: &SemanticError, src: &str) -> Self {
+        Self {
+            errors: vec![ErrorKind::semantic(error, src)],
+            suggestions: Vec::new(),
         }
     }
+
+    /// Add suggested fixes for the errors.
+    pub fn with_suggestions(mut self, suggestions: &[Suggestion]) -> Self {
+        self.suggestions.extend_from_slice(suggestions);
+        self
+    }
+
+    /// Suggested fixes for the errors.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
 }
 
 impl<'a> From<TokenizeError> for CompileError {
@@ -10,15 +10,29 @@ use crate::types::{
 
 /// Compile a map of Yul contracts to a map of bytecode contracts.
 pub fn compile(
-    mut contracts: NamedYulContracts,
+    contracts: NamedYulContracts,
     optimize: bool,
 ) -> Result<NamedBytecodeContracts, CompileError> {
-    contracts
-        .drain()
-        .map(|(name, yul_src)| {
-            compile_single_contract(&name, yul_src, optimize).map(|bytecode| (name, bytecode))
-        })
-        .collect::<Result<NamedBytecodeContracts, _>>()
+    Ok(compile_with_runtimes(contracts, optimize)?.0)
+}
+
+/// Compile a map of Yul contracts to maps of deployment and runtime bytecode
+/// contracts. The runtime bytecode is the code that is deployed on chain, i.e.
+/// the contract without its constructor.
+pub fn compile_with_runtimes(
+    mut contracts: NamedYulContracts,
+    optimize: bool,
+) -> Result<(NamedBytecodeContracts, NamedBytecodeContracts), CompileError> {
+    let mut bytecode_contracts = NamedBytecodeContracts::new();
+    let mut runtime_contracts = NamedBytecodeContracts::new();
+
+    for (name, yul_src) in contracts.drain() {
+        let (bytecode, runtime) = compile_single_contract_with_runtime(&name, yul_src, optimize)?;
+        bytecode_contracts.insert(name.clone(), bytecode);
+        runtime_contracts.insert(name, runtime);
+    }
+
+    Ok((bytecode_contracts, runtime_contracts))
 }
 
 /// Compiles a single Yul contract to bytecode.
@@ -27,6 +41,20 @@ pub fn compile_single_contract(
     yul_src: YulIr,
     optimize: bool,
 ) -> Result<Bytecode, CompileError> {
+    Ok(compile_single_contract_with_runtime(name, yul_src, optimize)?.0)
+}
+
+/// Compiles a single Yul contract to its deployment and runtime bytecode.
+///
+/// solc reports the bytecode of the single sub-object of a Yul object as its
+/// deployed bytecode, which for Fe contracts is the "runtime" object. Versions
+/// of solc that don't report it fall back to the deployment bytecode, which
+/// overestimates the runtime size.
+fn compile_single_contract_with_runtime(
+    name: &str,
+    yul_src: YulIr,
+    optimize: bool,
+) -> Result<(Bytecode, Bytecode), CompileError> {
     let solc_temp = include_str!("solc_temp.json");
     let input = solc_temp
         .replace("{optimizer_enabled}", &optimize.to_string())
@@ -34,15 +62,22 @@ pub fn compile_single_contract(
     let raw_output = solc::compile(&input);
     let output: serde_json::Value = serde_json::from_str(&raw_output)?;
 
-    let bytecode = output["contracts"]["input.yul"][name]["evm"]["bytecode"]["object"]
-        .to_string()
-        .replace("\"", "");
+    let evm = &output["contracts"]["input.yul"][name]["evm"];
+    let bytecode = evm["bytecode"]["object"].to_string().replace("\"", "");
 
     if bytecode == "null" {
         return Err(CompileError::str(&output.to_string()));
     }
 
-    Ok(bytecode)
+    let runtime = match evm["deployedBytecode"]["object"]
+        .to_string()
+        .replace("\"", "")
+    {
+        runtime if runtime == "null" || runtime.is_empty() => bytecode.clone(),
+        runtime => runtime,
+    };
+
+    Ok((bytecode, runtime))
 }
 
 #[test]
@@ -1,5 +1,11 @@
 //! Modules for compiling Fe and building ABIs.
 
+use crate::diagnostics::{
+    Diagnostic,
+    DiagnosticSink,
+    Severity,
+    VecSink,
+};
 use crate::errors::CompileError;
 use crate::types::{
     CompiledContract,
@@ -7,11 +13,19 @@ use crate::types::{
     FeSrc,
     NamedContracts,
 };
+use fe_parser::keywords::Keywords;
+use fe_parser::numeric::hex_grouping_warnings;
+use fe_parser::string_utils::{
+    detect_line_endings,
+    LineEndingReport,
+};
 
 pub mod abi;
+pub mod diagnostics;
 pub mod errors;
 #[cfg(feature = "solc-backend")]
 pub mod evm;
+pub mod size;
 pub mod types;
 pub mod yul;
 
@@ -20,14 +34,75 @@ pub mod yul;
 /// If `with_bytecode` is set to false, the compiler will skip the final Yul ->
 /// Bytecode pass. This is useful when debugging invalid Yul code.
 pub fn compile(
+    src: FeSrc,
+    with_bytecode: bool,
+    optimize: bool,
+) -> Result<CompiledModule, CompileError> {
+    compile_with_sink(src, with_bytecode, optimize, &mut VecSink::new())
+}
+
+/// Compiles the given Fe source code to all targets and emits diagnostics to
+/// `sink` as soon as they are found.
+///
+/// Warnings about the whole file are emitted first.  Other warnings are
+/// emitted in the order in which they appear in the source.  If compilation
+/// fails, the errors are emitted after all warnings and are also returned.
+pub fn compile_with_sink(
+    src: FeSrc,
+    with_bytecode: bool,
+    optimize: bool,
+    sink: &mut dyn DiagnosticSink,
+) -> Result<CompiledModule, CompileError> {
+    let result = compile_module(src, with_bytecode, optimize, sink);
+
+    if let Err(error) = &result {
+        for kind in &error.errors {
+            sink.emit(kind.into());
+        }
+    }
+
+    result
+}
+
+fn compile_module(
     src: FeSrc,
     _with_bytecode: bool,
     _optimize: bool,
+    sink: &mut dyn DiagnosticSink,
 ) -> Result<CompiledModule, CompileError> {
-    // parse source
-    let fe_tokens = fe_parser::get_parse_tokens(src)?;
+    // parse source, keeping the comments for the max_size attributes
+    let (fe_tokens, trivia) = fe_parser::get_parse_tokens_with_trivia(src)?;
+    #[cfg_attr(not(feature = "solc-backend"), allow(unused_variables))]
+    let (max_sizes, mut warnings) = size::declared_max_sizes(&fe_tokens, &trivia)?;
+
+    // emit warnings about the source tokens
+    warnings.extend(
+        Keywords::default()
+            .reserved_warnings(&fe_tokens)
+            .iter()
+            .map(Diagnostic::from)
+            .chain(
+                hex_grouping_warnings(&fe_tokens)
+                    .iter()
+                    .map(Diagnostic::from),
+            ),
+    );
+    if detect_line_endings(src) == LineEndingReport::Mixed {
+        warnings.push(Diagnostic {
+            severity: Severity::Warning,
+            message: "file mixes \"\\n\" and \"\\r\\n\" line endings".to_string(),
+            span: None,
+        });
+    }
+    warnings.sort_by_key(|diag| diag.span.map(|span| span.start));
+    for warning in warnings {
+        sink.emit(warning);
+    }
+
     let fe_module = fe_parser::parsers::file_input(&fe_tokens[..])
-        .map_err(|error| CompileError::str(&error.format_user(src)))?
+        .map_err(|error| {
+            CompileError::str(&error.format_user(src)).with_suggestions(error.suggestions())
+        })?
         .1
         .node;
 
@@ -35,23 +110,37 @@ pub fn compile(
     let json_abis = abi::build(&fe_module)?;
 
     // analyze source code
-    let context = fe_analyzer::analyze(&fe_module)
-        .map_err(|error| CompileError::str(&error.format_user(src)))?;
+    let context =
+        fe_analyzer::analyze(&fe_module).map_err(|error| CompileError::semantic(&error, src))?;
 
     // compile to yul
     let yul_contracts = yul::compile(context, &fe_module)?;
 
     // compile to bytecode if required
     #[cfg(feature = "solc-backend")]
-    let bytecode_contracts = if _with_bytecode {
-        match evm::compile(yul_contracts.clone(), _optimize) {
+    let (bytecode_contracts, runtime_contracts) = if _with_bytecode {
+        match evm::compile_with_runtimes(yul_contracts.clone(), _optimize) {
             Err(error) => panic!("Yul compilation failed: {}", error),
             Ok(contracts) => contracts,
         }
     } else {
-        std::collections::HashMap::new()
+        (
+            std::collections::HashMap::new(),
+            std::collections::HashMap::new(),
+        )
     };
 
+    // check the size of the code deployed for each contract, i.e. its runtime
+    // bytecode, against its declared or default limit
+    #[cfg(feature = "solc-backend")]
+    for (name, bytecode) in runtime_contracts.iter() {
+        let max_size = max_sizes
+            .get(name)
+            .copied()
+            .unwrap_or(size::DEFAULT_MAX_SIZE);
+        size::check_size(name, bytecode, max_size)?;
+    }
+
     // combine all of the named contract maps
     let contracts = json_abis
         .keys()
@@ -0,0 +1,261 @@
+//! Checks on the size of compiled contracts.
+//!
+//! The maximum size of a contract can be declared with a `#[max_size(...)]`
+//! attribute on the line before its definition.  Attributes are comments to
+//! the tokenizer, so they are read from the trivia tokens rather than the AST.
+//! Attributes that are malformed, unknown or not followed by a contract
+//! definition are ignored with a warning.
+
+use crate::diagnostics::{
+    Diagnostic,
+    Severity,
+};
+use crate::errors::CompileError;
+use fe_parser::tokenizer::{
+    Token,
+    TokenType,
+};
+use std::collections::HashMap;
+
+/// The maximum size of deployed contract code on mainnet (EIP-170).
+pub const DEFAULT_MAX_SIZE: usize = 24576;
+
+/// Return the sizes declared by `#[max_size(...)]` attributes, keyed by the
+/// name of the contract that follows each attribute, and warnings about any
+/// attributes that were ignored.
+///
+/// `tokens` and `trivia` are the output of `get_parse_tokens_with_trivia`.
+pub fn declared_max_sizes(
+    tokens: &[Token],
+    trivia: &[Token],
+) -> Result<(HashMap<String, usize>, Vec<Diagnostic>), CompileError> {
+    let mut sizes = HashMap::new();
+    let mut warnings = vec![];
+    let mut warn = |tok: &Token, message: String| {
+        warnings.push(Diagnostic {
+            severity: Severity::Warning,
+            message,
+            span: Some(tok.span),
+        })
+    };
+
+    for tok in trivia.iter() {
+        if tok.typ != TokenType::COMMENT || !tok.string.starts_with("#[") {
+            continue;
+        }
+
+        let arg = match attribute(tok.string) {
+            Some(("max_size", arg)) => arg,
+            Some((name, _)) => {
+                warn(tok, format!("ignoring unknown attribute \"{}\"", name));
+                continue;
+            }
+            None => {
+                warn(
+                    tok,
+                    format!(
+                        "ignoring malformed attribute \"{}\"; expected \"#[name(...)]\"",
+                        tok.string,
+                    ),
+                );
+                continue;
+            }
+        };
+        let size = arg
+            .map(|arg| arg.replace('_', ""))
+            .and_then(|arg| arg.parse::<usize>().ok())
+            .ok_or_else(|| {
+                CompileError::str(&format!(
+                    "invalid max_size attribute \"{}\"; expected a number of bytes",
+                    tok.string,
+                ))
+            })?;
+
+        // "pub"? "contract" NAME
+        let mut rest = tokens
+            .iter()
+            .skip_while(|next| next.span.start < tok.span.end)
+            .filter(|next| !next.is_layout())
+            .skip_while(|next| next.string == "pub");
+
+        match (rest.next(), rest.next()) {
+            (
+                Some(Token {
+                    string: "contract", ..
+                }),
+                Some(name_tok),
+            ) => {
+                sizes.insert(name_tok.string.to_owned(), size);
+            }
+            _ => warn(
+                tok,
+                format!(
+                    "ignoring attribute \"{}\", which is not followed by a contract definition",
+                    tok.string,
+                ),
+            ),
+        }
+    }
+
+    Ok((sizes, warnings))
+}
+
+/// Split a comment of the form `#[name]` or `#[name(arg)]` into the name and
+/// argument of the attribute, or return `None` if it is malformed.
+/// Whitespace is allowed between the parts of the attribute e.g.
+/// `#[max_size( 64 )]`.
+fn attribute(comment: &str) -> Option<(&str, Option<&str>)> {
+    let rest = comment
+        .strip_prefix("#[")?
+        .trim_end()
+        .strip_suffix(']')?
+        .trim();
+    let (name, arg) = match rest.find('(') {
+        Some(paren) => {
+            let arg = rest[paren + 1..].strip_suffix(')')?;
+            (rest[..paren].trim_end(), Some(arg.trim()))
+        }
+        None => (rest, None),
+    };
+
+    let is_name = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_name {
+        Some((name, arg))
+    } else {
+        None
+    }
+}
+
+/// Check that the hex encoded `bytecode` of the contract `name` is no larger
+/// than `max_size` bytes.
+pub fn check_size(name: &str, bytecode: &str, max_size: usize) -> Result<(), CompileError> {
+    let size = bytecode.trim_start_matches("0x").len() / 2;
+
+    if size > max_size {
+        return Err(CompileError::str(&format!(
+            "contract \"{}\" is {} bytes, which exceeds the maximum size of {} bytes; \
+             consider moving some of its functions into another contract",
+            name, size, max_size,
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ErrorKind;
+    use crate::size::{
+        check_size,
+        declared_max_sizes,
+        DEFAULT_MAX_SIZE,
+    };
+    use fe_parser::get_parse_tokens_with_trivia;
+    use fe_parser::span::Span;
+
+    /// Stand-in for code generation that produces `size` bytes of bytecode.
+    fn stub_bytecode(size: usize) -> String {
+        "00".repeat(size)
+    }
+
+    #[test]
+    fn test_check_size() {
+        assert!(check_size("Foo", &stub_bytecode(DEFAULT_MAX_SIZE), DEFAULT_MAX_SIZE).is_ok());
+
+        let error = check_size(
+            "Foo",
+            &stub_bytecode(DEFAULT_MAX_SIZE + 1),
+            DEFAULT_MAX_SIZE,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![ErrorKind::Str(
+                "contract \"Foo\" is 24577 bytes, which exceeds the maximum size of 24576 bytes; \
+                 consider moving some of its functions into another contract"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_declared_max_sizes() {
+        let src = "\
+#[max_size(64)]
+contract Foo:
+    x: u256
+
+# an ordinary comment
+contract Bar:
+    x: u256
+";
+        let (tokens, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+        let (sizes, warnings) = declared_max_sizes(&tokens, &trivia).unwrap();
+
+        assert_eq!(sizes.get("Foo"), Some(&64));
+        assert_eq!(sizes.get("Bar"), None);
+        assert_eq!(warnings, vec![]);
+
+        // the attribute overrides the default limit
+        let bytecode = stub_bytecode(100);
+        assert!(check_size("Foo", &bytecode, DEFAULT_MAX_SIZE).is_ok());
+        assert!(check_size("Foo", &bytecode, sizes["Foo"]).is_err());
+
+        // whitespace inside the attribute is ignored
+        let src = "#[ max_size( 1_024 ) ]\npub contract Foo:\n    x: u256\n";
+        let (tokens, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+        let (sizes, _) = declared_max_sizes(&tokens, &trivia).unwrap();
+
+        assert_eq!(sizes.get("Foo"), Some(&1024));
+    }
+
+    #[test]
+    fn test_declared_max_sizes_warnings() {
+        let warnings = |src: &str| {
+            let (tokens, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+            let (sizes, warnings) = declared_max_sizes(&tokens, &trivia).unwrap();
+            assert!(sizes.is_empty());
+
+            warnings
+                .into_iter()
+                .map(|warning| (warning.message, warning.span))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            warnings("#[maxsize(64)]\ncontract Foo:\n    x: u256\n"),
+            vec![(
+                "ignoring unknown attribute \"maxsize\"".to_string(),
+                Some(Span::new(0, 14)),
+            )],
+        );
+        assert_eq!(
+            warnings("#[max_size 64]\ncontract Foo:\n    x: u256\n"),
+            vec![(
+                "ignoring malformed attribute \"#[max_size 64]\"; expected \"#[name(...)]\""
+                    .to_string(),
+                Some(Span::new(0, 14)),
+            )],
+        );
+        assert_eq!(
+            warnings("#[max_size(64)]\nstruct Foo:\n    x: u256\n"),
+            vec![(
+                "ignoring attribute \"#[max_size(64)]\", which is not followed by a contract \
+                 definition"
+                    .to_string(),
+                Some(Span::new(0, 15)),
+            )],
+        );
+    }
+
+    #[test]
+    fn test_declared_max_sizes_errors() {
+        let src = "#[max_size(big)]\ncontract Foo:\n    x: u256\n";
+        let (tokens, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+        assert!(declared_max_sizes(&tokens, &trivia).is_err());
+
+        let src = "#[max_size]\ncontract Foo:\n    x: u256\n";
+        let (tokens, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+        assert!(declared_max_sizes(&tokens, &trivia).is_err());
+    }
+}
@@ -14,17 +14,17 @@ pub fn contract_def(
     stmt: &Spanned<fe::ModuleStmt>,
     created_contracts: Vec<yul::Object>,
 ) -> Result<yul::Object, CompileError> {
-    if let fe::ModuleStmt::ContractDef { name, body } = &stmt.node {
+    if let fe::ModuleStmt::ContractDef { name, body, .. } = &stmt.node {
         let mut init = None;
         let mut user_functions = vec![];
         let contract_name = name.node;
 
         // map user defined functions
         for stmt in body.iter() {
-            if let (Some(attributes), fe::ContractStmt::FuncDef { name, .. }) =
+            if let (Some(attributes), fe::ContractStmt::FuncDef { is_constructor, .. }) =
                 (context.get_function(stmt), &stmt.node)
             {
-                if name.node == "__init__" {
+                if *is_constructor {
                     init = Some((
                         functions::func_def(context, stmt)?,
                         attributes.param_types.clone(),
@@ -1,4 +1,5 @@
 use crate::errors::CompileError;
+use crate::yul::constants::numeric_min_max;
 use crate::yul::names;
 use crate::yul::operations::{
     abi as abi_operations,
@@ -23,6 +24,7 @@ use fe_analyzer::namespace::types::{
     Base,
     FeSized,
     FixedSize,
+    Integer,
     Type,
 };
 use fe_analyzer::{
@@ -42,7 +44,7 @@ pub fn expr(context: &Context, exp: &Spanned<fe::Expr>) -> Result<yul::Expressio
     if let Some(attributes) = context.get_expression(exp) {
         let expression = match &exp.node {
             fe::Expr::Name(_) => Ok(expr_name(exp)),
-            fe::Expr::Num(_) => expr_num(exp),
+            fe::Expr::Num { .. } => expr_num(exp),
             fe::Expr::Bool(_) => expr_bool(exp),
             fe::Expr::Subscript { .. } => expr_subscript(context, exp),
             fe::Expr::Attribute { .. } => expr_attribute(context, exp),
@@ -53,10 +55,15 @@ pub fn expr(context: &Context, exp: &Spanned<fe::Expr>) -> Result<yul::Expressio
             fe::Expr::CompOperation { .. } => expr_comp_operation(context, exp),
             fe::Expr::Call { .. } => expr_call(context, exp),
             fe::Expr::List { .. } => unimplemented!(),
+            fe::Expr::AnnotatedList { .. } => unreachable!(),
             fe::Expr::ListComp { .. } => unimplemented!(),
+            fe::Expr::ArrayRepeat { .. } => unreachable!(),
+            fe::Expr::Cast { .. } => unreachable!(),
+            fe::Expr::Comptime { inner } => expr(context, inner),
             fe::Expr::Tuple { .. } => expr_tuple(exp),
             fe::Expr::Str(_) => expr_str(exp),
             fe::Expr::Ellipsis => unimplemented!(),
+            fe::Expr::OperatorRef(_) => unreachable!(),
         }?;
 
         match (
@@ -105,7 +112,13 @@ pub fn call_arg(
 }
 
 fn expr_call(context: &Context, exp: &Spanned<fe::Expr>) -> Result<yul::Expression, CompileError> {
-    if let fe::Expr::Call { args, func } = &exp.node {
+    // Calls with modifiers are rejected by the analyzer.
+    if let fe::Expr::Call {
+        args,
+        func,
+        call_modifiers: _,
+    } = &exp.node
+    {
         if let Some(call_type) = context.get_call(func) {
             let yul_args: Vec<yul::Expression> = args
                 .node
@@ -286,6 +299,23 @@ pub fn expr_bin_operation(
                 false => Ok(expression! { mod([yul_left], [yul_right]) }),
             },
             fe::BinOperator::Pow => Ok(expression! { exp([yul_left], [yul_right]) }),
+            fe::BinOperator::WrappingAdd
+            | fe::BinOperator::WrappingSub
+            | fe::BinOperator::WrappingMult => match typ {
+                Type::Base(Base::Numeric(integer)) => {
+                    let result = match op.node {
+                        fe::BinOperator::WrappingAdd => {
+                            expression! { add([yul_left], [yul_right]) }
+                        }
+                        fe::BinOperator::WrappingSub => {
+                            expression! { sub([yul_left], [yul_right]) }
+                        }
+                        _ => expression! { mul([yul_left], [yul_right]) },
+                    };
+                    Ok(wrap_to_integer(integer, result))
+                }
+                _ => unreachable!(),
+            },
             _ => unimplemented!(),
         };
     }
@@ -293,6 +323,22 @@ pub fn expr_bin_operation(
     unreachable!()
 }
 
+/// Truncate a 256 bit result to the width of `integer`, sign-extending it if
+/// the integer is signed.
+fn wrap_to_integer(integer: &Integer, val: yul::Expression) -> yul::Expression {
+    let size = integer.size();
+
+    if size == 32 {
+        val
+    } else if integer.is_signed() {
+        let byte = literal_expression! { (size - 1) };
+        expression! { signextend([byte], [val]) }
+    } else {
+        let mask = numeric_min_max()[integer].1.clone();
+        expression! { and([val], [mask]) }
+    }
+}
+
 pub fn expr_unary_operation(
     context: &Context,
     exp: &Spanned<fe::Expr>,
@@ -365,7 +411,7 @@ fn expr_name(exp: &Spanned<fe::Expr>) -> yul::Expression {
 }
 
 fn expr_num(exp: &Spanned<fe::Expr>) -> Result<yul::Expression, CompileError> {
-    if let fe::Expr::Num(num) = &exp.node {
+    if let fe::Expr::Num { value: num, .. } = &exp.node {
         return Ok(literal_expression! {(num)});
     }
 
@@ -37,11 +37,16 @@ pub fn func_def(
     if let (
         Some(attributes),
         fe::ContractStmt::FuncDef {
+            decorators: _,
             qual: _,
             name,
+            generic_params: _,
             args,
             return_type: _,
+            named_returns: _,
             body,
+            is_constructor: _,
+            init_only: _,
         },
     ) = (context.get_function(def).to_owned(), &def.node)
     {
@@ -86,6 +91,8 @@ fn func_stmt(
         fe::FuncStmt::For { .. } => for_loop(context, stmt),
         fe::FuncStmt::While { .. } => while_loop(context, stmt),
         fe::FuncStmt::If { .. } => if_statement(context, stmt),
+        fe::FuncStmt::Unchecked { .. } => unreachable!(),
+        fe::FuncStmt::Yul { .. } => unreachable!(),
         fe::FuncStmt::Assert { .. } => assert(context, stmt),
         fe::FuncStmt::Expr { .. } => expr(context, stmt),
         fe::FuncStmt::Pass => Ok(statement! { pop(0) }),
@@ -181,7 +188,7 @@ fn revert(stmt: &Spanned<fe::FuncStmt>) -> Result<yul::Statement, CompileError>
 
 fn emit(context: &Context, stmt: &Spanned<fe::FuncStmt>) -> Result<yul::Statement, CompileError> {
     if let fe::FuncStmt::Emit { value } = &stmt.node {
-        if let fe::Expr::Call { func: _, args } = &value.node {
+        if let fe::Expr::Call { args, .. } = &value.node {
             let event_values = args
                 .node
                 .iter()
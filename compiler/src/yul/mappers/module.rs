@@ -33,8 +33,14 @@ pub fn module(context: &Context, module: &fe::Module) -> Result<YulContracts, Co
                     }
                 }
                 fe::ModuleStmt::StructDef { .. } => {}
+                fe::ModuleStmt::InterfaceDef { .. } => unreachable!(),
                 fe::ModuleStmt::FromImport { .. } => unimplemented!(),
                 fe::ModuleStmt::SimpleImport { .. } => unimplemented!(),
+                fe::ModuleStmt::Export { .. } => unreachable!(),
+                // Constants are folded by the analyzer and generate no code.
+                fe::ModuleStmt::ConstDef { .. }
+                | fe::ModuleStmt::Docstring { .. }
+                | fe::ModuleStmt::StaticAssert { .. } => {}
             }
 
             Ok(contracts)
@@ -67,7 +67,11 @@ pub fn mcopym<T: FeSized>(typ: T, ptr: yul::Expression) -> yul::Expression {
 
 /// Logs an event.
 pub fn emit_event(event: Event, vals: Vec<yul::Expression>) -> yul::Statement {
-    let mut topics = vec![literal_expression! { (event.topic) }];
+    let mut topics = if event.anonymous {
+        vec![]
+    } else {
+        vec![literal_expression! { (event.topic) }]
+    };
 
     let (field_vals, field_types): (Vec<yul::Expression>, Vec<FixedSize>) = event
         .non_indexed_fields()
@@ -142,6 +146,7 @@ mod tests {
             "MyEvent",
             vec![FixedSize::Base(U256), FixedSize::Base(Base::Address)],
             vec![],
+            false,
         );
 
         assert_eq!(
@@ -156,6 +161,7 @@ mod tests {
             "MyEvent",
             vec![FixedSize::Base(U256), FixedSize::Base(Base::Address)],
             vec![0],
+            false,
         );
 
         assert_eq!(
@@ -164,6 +170,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_emit_event_anonymous() {
+        let event = Event::new(
+            "MyEvent",
+            vec![FixedSize::Base(U256), FixedSize::Base(Base::Address)],
+            vec![0],
+            true,
+        );
+
+        assert_eq!(
+            emit_event(event, vec![expression! { 26 }, expression! { 0x00 }]).to_string(),
+            "log1(abi_encode_address(0x00), add(32, 0), 26)"
+        )
+    }
+
     #[test]
     fn test_sum() {
         assert_eq!(
@@ -2,6 +2,14 @@
 
 #![cfg(feature = "solc-backend")]
 
+use fe_compiler::diagnostics::{
+    Diagnostic,
+    DiagnosticSink,
+    Severity,
+};
+use fe_compiler::errors::ErrorKind;
+use fe_parser::errors::Suggestion;
+use fe_parser::span::Span;
 use rstest::rstest;
 use std::fs;
 
@@ -66,7 +74,13 @@ use std::fs;
     case("external_call_type_error.fe", "TypeError"),
     case("external_call_wrong_number_of_params.fe", "WrongNumberOfParams"),
     case("non_bool_and.fe", "TypeError"),
-    case("non_bool_or.fe", "TypeError")
+    case("non_bool_or.fe", "TypeError"),
+    case("float_literal.fe", "FloatLiteral"),
+    case("static_assert_failed.fe", "StaticAssertionFailed"),
+    case("static_assert_non_const.fe", "NonConstExpression"),
+    case("array_repeat.fe", "NotSupported"),
+    case("unchecked_block.fe", "NotSupported"),
+    case("export.fe", "NotSupported")
 )]
 fn test_compile_errors(fixture_file: &str, expected_error: &str) {
     let src = fs::read_to_string(format!("tests/fixtures/compile_errors/{}", fixture_file))
@@ -85,3 +99,86 @@ fn test_compile_errors(fixture_file: &str, expected_error: &str) {
         ),
     }
 }
+
+#[test]
+fn test_compile_error_variants() {
+    let compile_fixture = |fixture_file: &str| {
+        let src = fs::read_to_string(format!("tests/fixtures/compile_errors/{}", fixture_file))
+            .expect("Unable to read fixture file");
+
+        match fe_compiler::compile(&src, false, false) {
+            Err(mut compile_error) => compile_error.errors.remove(0),
+            _ => panic!(
+                "Compiling {} succeeded when it was expected to fail",
+                fixture_file
+            ),
+        }
+    };
+
+    let error = compile_fixture("mismatch_return_type.fe");
+    assert!(matches!(
+        error,
+        ErrorKind::TypeError { span, .. } if span == Span::new(53, 61)
+    ));
+    assert_eq!(error.code(), 10);
+
+    let error = compile_fixture("missing_return.fe");
+    assert!(matches!(
+        error,
+        ErrorKind::MissingReturn { span, .. } if span == Span::new(44, 90)
+    ));
+    assert_eq!(error.code(), 4);
+    assert!(format!("{}", error).starts_with("error[E0004]: MissingReturn"));
+}
+
+#[test]
+fn test_compile_with_sink() {
+    struct RecordingSink(Vec<Diagnostic>);
+
+    impl DiagnosticSink for RecordingSink {
+        fn emit(&mut self, diag: Diagnostic) {
+            self.0.push(diag)
+        }
+    }
+
+    let src = "contract Foo:
+    pub def bar() -> bool:
+        match: u256 = 1
+        return match
+
+    pub def baz():
+        trait: u256 = 0xd_ead
+";
+    let mut sink = RecordingSink(vec![]);
+    let result = fe_compiler::compile_with_sink(src, false, false, &mut sink);
+
+    assert!(result.is_err());
+    assert_eq!(
+        sink.0
+            .iter()
+            .map(|diag| (diag.severity, diag.span))
+            .collect::<Vec<_>>(),
+        vec![
+            (Severity::Warning, Some(Span::new(49, 54))),
+            (Severity::Warning, Some(Span::new(80, 85))),
+            (Severity::Warning, Some(Span::new(114, 119))),
+            (Severity::Warning, Some(Span::new(128, 135))),
+            (Severity::Error, Some(Span::new(73, 85))),
+        ],
+    );
+    assert!(sink.0[0].message.starts_with("\"match\" is reserved"));
+    assert!(sink.0[3].message.contains("0xd_ead"));
+    assert!(sink.0[4].message.starts_with("error[E0010]: TypeError"));
+}
+
+#[test]
+fn test_compile_error_suggestions() {
+    let src = "struct Foo\n    x: u256\n";
+
+    match fe_compiler::compile(src, false, false) {
+        Err(compile_error) => {
+            assert_eq!(compile_error.suggestions(), &[Suggestion::insert(10, ":")],)
+        }
+        _ => panic!("Compiling succeeded when it was expected to fail"),
+    }
+}
@@ -133,6 +133,12 @@ fn test_assert() {
     case("return_multiplication_u256.fe", &[uint_token(42), uint_token(42)], uint_token(1764)),
     case("return_multiplication_i256.fe", &[int_token(-42), int_token(-42)], int_token(1764)),
     case("return_multiplication_i256.fe", &[int_token(-42), int_token(42)], int_token(-1764)),
+    case("return_wrapping_add_u8.fe", &[uint_token(200), uint_token(42)], uint_token(242)),
+    case("return_wrapping_add_u8.fe", &[uint_token(255), uint_token(1)], uint_token(0)),
+    case("return_wrapping_add_u8.fe", &[uint_token(200), uint_token(100)], uint_token(44)),
+    case("return_wrapping_mult_i8.fe", &[int_token(-4), int_token(8)], int_token(-32)),
+    case("return_wrapping_mult_i8.fe", &[int_token(16), int_token(8)], int_token(-128)),
+    case("return_wrapping_mult_i8.fe", &[int_token(-16), int_token(-16)], int_token(0)),
     case("return_division_u256.fe", &[uint_token(42), uint_token(42)], uint_token(1)),
     case("return_division_i256.fe", &[int_token(-42), int_token(-42)], int_token(1)),
     case("return_division_i256.fe", &[int_token(-1), int_token(1)], int_token(-1)),
@@ -1027,3 +1033,31 @@ fn self_address() {
         );
     });
 }
+
+#[rstest(
+    fixture_file,
+    case("strings.fe"),
+    case("events.fe"),
+    case("structs.fe"),
+    case("create_contract.fe"),
+    case("external_contract.fe")
+)]
+fn deterministic_bytecode(fixture_file: &str) {
+    let src = std::fs::read_to_string(format!("tests/fixtures/features/{}", fixture_file))
+        .expect("unable to read fixture file");
+
+    let first = fe_compiler::compile(&src, true, true).expect("failed to compile module");
+    let second = fe_compiler::compile(&src, true, true).expect("failed to compile module");
+
+    assert_eq!(first.contracts.len(), second.contracts.len());
+    for (name, contract) in first.contracts.iter() {
+        let other = &second.contracts[name];
+
+        assert_eq!(contract.yul, other.yul, "yul of {} differs", name);
+        assert_eq!(
+            contract.bytecode, other.bytecode,
+            "bytecode of {} differs",
+            name
+        );
+    }
+}
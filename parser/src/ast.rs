@@ -3,7 +3,13 @@ use serde::{
     Serialize,
 };
 
-use crate::span::Spanned;
+use crate::span::{
+    Span,
+    Spanned,
+};
+use crate::string_utils::decode_string;
+use std::iter::FilterMap;
+use std::slice::Iter;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Module<'a> {
@@ -11,6 +17,76 @@ pub struct Module<'a> {
     pub body: Vec<Spanned<ModuleStmt<'a>>>,
 }
 
+impl<'a> Module<'a> {
+    /// Return the decoded docstring of the module, if any.
+    pub fn doc(&self) -> Option<String> {
+        match self.body.first().map(|stmt| &stmt.node) {
+            Some(ModuleStmt::Docstring { lines }) => Some(decode_string(lines)),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the values that `f` maps the module's statements to,
+    /// skipping statements for which `f` returns `None`.
+    pub fn filter_map_stmts<'b, T, F>(
+        &'b self,
+        f: F,
+    ) -> FilterMap<Iter<'b, Spanned<ModuleStmt<'a>>>, F>
+    where
+        F: Fn(&'b Spanned<ModuleStmt<'a>>) -> Option<T>,
+    {
+        self.body.iter().filter_map(f)
+    }
+
+    /// Iterate over the struct definitions in the module.
+    pub fn structs(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::StructDef { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the contract definitions in the module.
+    pub fn contracts(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::ContractDef { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the type aliases in the module.
+    pub fn type_aliases(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::TypeDef { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the constant definitions in the module.
+    pub fn consts(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::ConstDef { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the interface definitions in the module.
+    pub fn interfaces(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::InterfaceDef { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the import statements in the module.
+    pub fn imports(&self) -> impl Iterator<Item = &Spanned<ModuleStmt<'a>>> {
+        self.filter_map_stmts(|stmt| match stmt.node {
+            ModuleStmt::SimpleImport { .. } | ModuleStmt::FromImport { .. } => Some(stmt),
+            _ => None,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum ModuleStmt<'a> {
     TypeDef {
@@ -18,6 +94,14 @@ pub enum ModuleStmt<'a> {
         #[serde(borrow)]
         typ: Spanned<TypeDesc<'a>>,
     },
+    /// A module-level constant e.g. "const MAX: u256 = 2 ** 8".
+    ConstDef {
+        name: Spanned<&'a str>,
+        #[serde(borrow)]
+        typ: Spanned<TypeDesc<'a>>,
+        #[serde(borrow)]
+        value: Spanned<Expr<'a>>,
+    },
     SimpleImport {
         #[serde(borrow)]
         names: Vec<Spanned<SimpleImportName<'a>>>,
@@ -28,18 +112,60 @@ pub enum ModuleStmt<'a> {
         #[serde(borrow)]
         names: Spanned<FromImportNames<'a>>,
     },
+    Export {
+        #[serde(borrow)]
+        name: Spanned<ExportName<'a>>,
+    },
     ContractDef {
         name: Spanned<&'a str>,
+        /// The interfaces implemented by the contract e.g. "ERC20" in
+        /// "contract Token(ERC20):".
+        base: Vec<Spanned<&'a str>>,
         #[serde(borrow)]
         body: Vec<Spanned<ContractStmt<'a>>>,
+        pub_: bool,
     },
     StructDef {
         name: Spanned<&'a str>,
         #[serde(borrow)]
         body: Vec<Spanned<StructStmt<'a>>>,
+        /// True if the struct was declared without a body e.g. "struct Marker".
+        is_marker: bool,
+    },
+    /// A set of function signatures that contracts may implement.
+    InterfaceDef {
+        name: Spanned<&'a str>,
+        #[serde(borrow)]
+        functions: Vec<Spanned<FuncSig<'a>>>,
+    },
+    /// A documentation string that precedes all other statements.
+    Docstring {
+        #[serde(borrow)]
+        lines: Vec<&'a str>,
+    },
+    /// A condition checked at compile time with an optional failure message.
+    StaticAssert {
+        #[serde(borrow)]
+        cond: Spanned<Expr<'a>>,
+        #[serde(borrow)]
+        msg: Option<Spanned<Vec<&'a str>>>,
     },
 }
 
+impl<'a> ModuleStmt<'a> {
+    /// Return the decoded docstring of a contract definition.  Other module
+    /// statements do not have docstrings.
+    pub fn doc(&self) -> Option<String> {
+        if let ModuleStmt::ContractDef { body, .. } = self {
+            if let Some(ContractStmt::Docstring { lines }) = body.first().map(|stmt| &stmt.node) {
+                return Some(decode_string(lines));
+            }
+        }
+
+        None
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum TypeDesc<'a> {
     Base {
@@ -49,6 +175,15 @@ pub enum TypeDesc<'a> {
         typ: Box<Spanned<TypeDesc<'a>>>,
         dimension: usize,
     },
+    /// An integer type with an explicit bit width.
+    Int {
+        signed: bool,
+        bits: usize,
+    },
+    GenericArray {
+        typ: Box<Spanned<TypeDesc<'a>>>,
+        dimension: &'a str,
+    },
     Map {
         from: Box<Spanned<TypeDesc<'a>>>,
         to: Box<Spanned<TypeDesc<'a>>>,
@@ -56,6 +191,15 @@ pub enum TypeDesc<'a> {
     Tuple {
         items: Vec<Spanned<TypeDesc<'a>>>,
     },
+    /// The return type of functions that never return.
+    Never,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum ExportName<'a> {
+    Star,
+    #[serde(borrow)]
+    Path(SimpleImportName<'a>),
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -72,7 +216,9 @@ pub enum FromImportPath<'a> {
         path: Vec<Spanned<&'a str>>,
     },
     Relative {
-        parent_level: usize,
+        /// The number of leading dots e.g. 2 in "from ..foo import bar".  A
+        /// single dot refers to the importing module's own package.
+        dots: usize,
         #[serde(borrow)]
         path: Vec<Spanned<&'a str>>,
     },
@@ -103,14 +249,77 @@ pub enum ContractStmt<'a> {
     EventDef {
         name: Spanned<&'a str>,
         fields: Vec<Spanned<EventField<'a>>>,
+        /// True if the event was declared with the "anonymous" modifier.
+        /// Anonymous events are logged without their signature as a topic.
+        anonymous: bool,
+        /// The lines of a docstring preceding the event's fields, if any.
+        #[serde(borrow)]
+        doc: Option<Vec<&'a str>>,
     },
     FuncDef {
+        /// The names of any decorators preceding the definition e.g. "payable"
+        /// in "@payable".
+        decorators: Vec<Spanned<&'a str>>,
         qual: Option<Spanned<FuncQual>>,
         name: Spanned<&'a str>,
+        generic_params: Vec<Spanned<GenericParameter<'a>>>,
         args: Vec<Spanned<FuncDefArg<'a>>>,
         return_type: Option<Spanned<TypeDesc<'a>>>,
+        /// The names and types of named return values e.g. "total" in
+        /// "-> (total: u256)".  Empty if the return values are unnamed.
+        named_returns: Vec<Spanned<FuncDefArg<'a>>>,
         body: Vec<Spanned<FuncStmt<'a>>>,
+        is_constructor: bool,
+        init_only: bool,
+    },
+    /// A struct declared inside a contract.
+    StructDef {
+        name: Spanned<&'a str>,
+        #[serde(borrow)]
+        body: Vec<Spanned<StructStmt<'a>>>,
+        is_marker: bool,
+    },
+    /// A documentation string that precedes all other statements.
+    Docstring {
+        #[serde(borrow)]
+        lines: Vec<&'a str>,
+    },
+    /// A condition checked at compile time with an optional failure message.
+    StaticAssert {
+        #[serde(borrow)]
+        cond: Spanned<Expr<'a>>,
+        #[serde(borrow)]
+        msg: Option<Spanned<Vec<&'a str>>>,
     },
+    /// Contract fields that should be packed into as few storage slots as
+    /// possible.  Only `ContractField` statements appear in a group.
+    PackedGroup {
+        #[serde(borrow)]
+        fields: Vec<Spanned<ContractStmt<'a>>>,
+    },
+}
+
+impl<'a> ContractStmt<'a> {
+    /// Return the decoded docstring of a function or event.  A function's
+    /// docstring is a string expression at the start of its body and an
+    /// event's docstring precedes its fields.  Other contract statements do
+    /// not have docstrings.
+    pub fn doc(&self) -> Option<String> {
+        match self {
+            ContractStmt::FuncDef { body, .. } => match body.first() {
+                Some(Spanned {
+                    node:
+                        FuncStmt::Expr {
+                            value: Expr::Str(lines),
+                        },
+                    ..
+                }) => Some(decode_string(lines)),
+                _ => None,
+            },
+            ContractStmt::EventDef { doc, .. } => doc.as_ref().map(|lines| decode_string(lines)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -153,6 +362,35 @@ pub enum FuncQual {
     Pub,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum GenericParameter<'a> {
+    Type {
+        #[serde(borrow)]
+        name: Spanned<&'a str>,
+    },
+    Const {
+        #[serde(borrow)]
+        name: Spanned<&'a str>,
+        typ: Spanned<TypeDesc<'a>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum ArrayDim<'a> {
+    Fixed(usize),
+    Generic(&'a str),
+}
+
+/// A function signature without a body e.g. "def transfer(to: address) ->
+/// bool".
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FuncSig<'a> {
+    #[serde(borrow)]
+    pub name: Spanned<&'a str>,
+    pub args: Vec<Spanned<FuncDefArg<'a>>>,
+    pub return_type: Option<Spanned<TypeDesc<'a>>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct FuncDefArg<'a> {
     #[serde(borrow)]
@@ -196,6 +434,14 @@ pub enum FuncStmt<'a> {
         body: Vec<Spanned<FuncStmt<'a>>>,
         or_else: Vec<Spanned<FuncStmt<'a>>>,
     },
+    Unchecked {
+        body: Vec<Spanned<FuncStmt<'a>>>,
+    },
+    /// A block of inline Yul code.  The code is not parsed by Fe and is
+    /// passed to the backend as written.
+    Yul {
+        source_span: Span,
+    },
     Assert {
         test: Spanned<Expr<'a>>,
         msg: Option<Spanned<Expr<'a>>>,
@@ -246,13 +492,36 @@ pub enum Expr<'a> {
         value: Box<Spanned<Expr<'a>>>,
         slices: Spanned<Vec<Spanned<Slice<'a>>>>,
     },
+    /// A type cast e.g. "x as u8".
+    Cast {
+        value: Box<Spanned<Expr<'a>>>,
+        typ: Spanned<TypeDesc<'a>>,
+        mode: CastMode,
+    },
     Call {
         func: Box<Spanned<Expr<'a>>>,
+        call_modifiers: Vec<Spanned<CallModifier<'a>>>,
         args: Spanned<Vec<Spanned<CallArg<'a>>>>,
     },
     List {
         elts: Vec<Spanned<Expr<'a>>>,
     },
+    /// An array literal with an element type annotation e.g.
+    /// "[1, 2, 3]: u8[3]".
+    AnnotatedList {
+        elts: Vec<Spanned<Expr<'a>>>,
+        typ: Spanned<TypeDesc<'a>>,
+    },
+    /// An array literal with a repeated value e.g. "[0; 32]".
+    ArrayRepeat {
+        value: Box<Spanned<Expr<'a>>>,
+        count: Box<Spanned<Expr<'a>>>,
+    },
+    /// An expression that must be evaluated at compile time e.g.
+    /// "comptime(2 ** 8)".
+    Comptime {
+        inner: Box<Spanned<Expr<'a>>>,
+    },
     ListComp {
         elt: Box<Spanned<Expr<'a>>>,
         comps: Vec<Spanned<Comprehension<'a>>>,
@@ -262,9 +531,15 @@ pub enum Expr<'a> {
     },
     Bool(bool),
     Name(&'a str),
-    Num(&'a str),
+    /// A numeric literal.  Floats have a fractional part or an exponent e.g.
+    /// "1.5" or "1e10".
+    Num {
+        value: &'a str,
+        is_float: bool,
+    },
     Str(Vec<&'a str>),
     Ellipsis,
+    OperatorRef(Operator),
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -292,6 +567,13 @@ pub struct Kwarg<'a> {
     pub value: Box<Spanned<Expr<'a>>>,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct CallModifier<'a> {
+    #[serde(borrow)]
+    pub name: Spanned<&'a str>,
+    pub value: Box<Spanned<Expr<'a>>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Comprehension<'a> {
     #[serde(borrow)]
@@ -320,6 +602,9 @@ pub enum BinOperator {
     BitXor,
     BitAnd,
     FloorDiv,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMult,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -343,3 +628,22 @@ pub enum CompOperator {
     In,
     NotIn,
 }
+
+/// How a cast treats values that do not fit in the target type.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum CastMode {
+    /// Reverts if the value does not fit e.g. "x as u8".
+    Checked,
+    /// The same as `Checked`, but written out explicitly e.g. "x as? u8".
+    ExplicitChecked,
+    /// Truncates the value to fit e.g. "x as! u8".
+    Unchecked,
+}
+
+/// A binary operator that is referred to as a value e.g. "(+)".
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum Operator {
+    Bin(BinOperator),
+    Bool(BoolOperator),
+    Comp(CompOperator),
+}
@@ -120,6 +120,9 @@ impl TryFrom<&Token<'_>> for Spanned<BinOperator> {
             "^" => BitXor,
             "&" => BitAnd,
             "//" => FloorDiv,
+            "+%" => WrappingAdd,
+            "-%" => WrappingSub,
+            "*%" => WrappingMult,
             "+=" => Add,
             "-=" => Sub,
             "*=" => Mult,
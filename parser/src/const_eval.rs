@@ -0,0 +1,376 @@
+//! Evaluation of constant expressions at compile time, e.g. for array sizes,
+//! constant definitions and static assertions.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use num_bigint::BigInt;
+
+use crate::ast::{
+    BinOperator,
+    BoolOperator,
+    CompOperator,
+    Expr,
+    UnaryOperator,
+};
+use crate::errors::Diagnostic;
+use crate::numeric::strip_separators;
+use crate::span::{
+    Span,
+    Spanned,
+};
+
+/// A value computed at compile time.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstValue {
+    Int(BigInt),
+    Bool(bool),
+}
+
+/// An error encountered while evaluating a constant expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstEvalError {
+    DivisionByZero {
+        span: Span,
+    },
+    UnknownName {
+        name: String,
+        span: Span,
+    },
+    /// The result of an operation or a literal does not fit into 256 bits,
+    /// either as a signed or as an unsigned integer.
+    Overflow {
+        span: Span,
+    },
+    /// A floating-point literal was used where an integer is required.
+    FloatLiteral {
+        span: Span,
+    },
+    /// An operator was applied to a value of the wrong type e.g. `1 + true`.
+    TypeMismatch {
+        span: Span,
+    },
+    /// The expression is not made up of literals, names and operators.
+    NotConstant {
+        span: Span,
+    },
+}
+
+impl ConstEvalError {
+    pub fn span(&self) -> Span {
+        match self {
+            ConstEvalError::DivisionByZero { span }
+            | ConstEvalError::UnknownName { span, .. }
+            | ConstEvalError::Overflow { span }
+            | ConstEvalError::FloatLiteral { span }
+            | ConstEvalError::TypeMismatch { span }
+            | ConstEvalError::NotConstant { span } => *span,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            ConstEvalError::DivisionByZero { .. } => "division by zero".to_string(),
+            ConstEvalError::UnknownName { name, .. } => format!("unknown constant \"{}\"", name),
+            ConstEvalError::Overflow { .. } => "integer overflow".to_string(),
+            ConstEvalError::FloatLiteral { .. } => {
+                "expected an integer, found a floating-point literal".to_string()
+            }
+            ConstEvalError::TypeMismatch { .. } => "mismatched types".to_string(),
+            ConstEvalError::NotConstant { .. } => "expression is not constant".to_string(),
+        }
+    }
+}
+
+impl From<ConstEvalError> for Diagnostic {
+    fn from(error: ConstEvalError) -> Self {
+        Self {
+            message: error.message(),
+            span: error.span(),
+        }
+    }
+}
+
+/// Fold an expression made up of integer and boolean literals, names of
+/// constants in `env`, and arithmetic, bitwise, boolean and comparison
+/// operators into a value.
+///
+/// Integers are unbounded while folding, but every literal and intermediate
+/// result must fit into 256 bits.
+pub fn eval_const(
+    expr: &Spanned<Expr>,
+    env: &HashMap<String, ConstValue>,
+) -> Result<ConstValue, ConstEvalError> {
+    let span = expr.span;
+
+    match &expr.node {
+        Expr::Num { is_float: true, .. } => Err(ConstEvalError::FloatLiteral { span }),
+        Expr::Num { value, .. } => parse_int(value, span).map(ConstValue::Int),
+        Expr::Bool(val) => Ok(ConstValue::Bool(*val)),
+        Expr::Name(name) => env
+            .get(*name)
+            .cloned()
+            .ok_or_else(|| ConstEvalError::UnknownName {
+                name: (*name).to_string(),
+                span,
+            }),
+        Expr::Comptime { inner } => eval_const(inner, env),
+        Expr::UnaryOperation { op, operand } => match (&op.node, eval_const(operand, env)?) {
+            (UnaryOperator::Not, ConstValue::Bool(val)) => Ok(ConstValue::Bool(!val)),
+            (UnaryOperator::UAdd, ConstValue::Int(val)) => Ok(ConstValue::Int(val)),
+            (UnaryOperator::USub, ConstValue::Int(val)) => {
+                check_bounds(-val, span).map(ConstValue::Int)
+            }
+            (UnaryOperator::Invert, ConstValue::Int(val)) => {
+                check_bounds(!val, span).map(ConstValue::Int)
+            }
+            _ => Err(ConstEvalError::TypeMismatch { span }),
+        },
+        Expr::BoolOperation { left, op, right } => {
+            match (eval_const(left, env)?, eval_const(right, env)?) {
+                (ConstValue::Bool(left), ConstValue::Bool(right)) => {
+                    Ok(ConstValue::Bool(match op.node {
+                        BoolOperator::And => left && right,
+                        BoolOperator::Or => left || right,
+                    }))
+                }
+                _ => Err(ConstEvalError::TypeMismatch { span }),
+            }
+        }
+        Expr::BinOperation { left, op, right } => {
+            match (eval_const(left, env)?, eval_const(right, env)?) {
+                (ConstValue::Int(left), ConstValue::Int(right)) => {
+                    eval_bin_operation(left, op, right, span).map(ConstValue::Int)
+                }
+                _ => Err(ConstEvalError::TypeMismatch { span }),
+            }
+        }
+        Expr::CompOperation { left, op, right } => {
+            let result = match (&op.node, eval_const(left, env)?, eval_const(right, env)?) {
+                (CompOperator::Eq, left, right) => left == right,
+                (CompOperator::NotEq, left, right) => left != right,
+                (CompOperator::Lt, ConstValue::Int(left), ConstValue::Int(right)) => left < right,
+                (CompOperator::LtE, ConstValue::Int(left), ConstValue::Int(right)) => left <= right,
+                (CompOperator::Gt, ConstValue::Int(left), ConstValue::Int(right)) => left > right,
+                (CompOperator::GtE, ConstValue::Int(left), ConstValue::Int(right)) => left >= right,
+                _ => return Err(ConstEvalError::TypeMismatch { span }),
+            };
+
+            Ok(ConstValue::Bool(result))
+        }
+        _ => Err(ConstEvalError::NotConstant { span }),
+    }
+}
+
+fn eval_bin_operation(
+    left: BigInt,
+    op: &Spanned<BinOperator>,
+    right: BigInt,
+    span: Span,
+) -> Result<BigInt, ConstEvalError> {
+    let zero = BigInt::from(0);
+    let one = BigInt::from(1);
+    let minus_one = BigInt::from(-1);
+    // Exponents and shift amounts above this overflow 256 bits for all but
+    // trivial operands, so they are rejected before computing the result.
+    let max_bits = BigInt::from(256);
+
+    let result = match op.node {
+        BinOperator::Add | BinOperator::WrappingAdd => left + right,
+        BinOperator::Sub | BinOperator::WrappingSub => left - right,
+        BinOperator::Mult | BinOperator::WrappingMult => left * right,
+        BinOperator::Div | BinOperator::FloorDiv | BinOperator::Mod if right == zero => {
+            return Err(ConstEvalError::DivisionByZero { span })
+        }
+        BinOperator::Div | BinOperator::FloorDiv => left / right,
+        BinOperator::Mod => left % right,
+        BinOperator::Pow | BinOperator::LShift | BinOperator::RShift if right < zero => {
+            return Err(ConstEvalError::NotConstant { span })
+        }
+        // Overflowing powers are reported at the `**` operator, since the
+        // operands alone are usually small
+        BinOperator::Pow if right > max_bits && (left < minus_one || left > one) => {
+            return Err(ConstEvalError::Overflow { span: op.span })
+        }
+        BinOperator::Pow => {
+            // The powers of 0, 1 and -1 repeat with a period of 2
+            let exponent = if right > max_bits {
+                right % 2 + 2
+            } else {
+                right
+            };
+            let result = left.pow(u32::try_from(exponent).expect("exponent out of range"));
+
+            return check_bounds(result, op.span);
+        }
+        BinOperator::LShift if right > max_bits && left != zero => {
+            return Err(ConstEvalError::Overflow { span })
+        }
+        BinOperator::LShift | BinOperator::RShift => {
+            let bits = usize::try_from(right.min(max_bits)).expect("shift out of range");
+            match op.node {
+                BinOperator::LShift => left << bits,
+                _ => left >> bits,
+            }
+        }
+        BinOperator::BitOr => left | right,
+        BinOperator::BitXor => left ^ right,
+        BinOperator::BitAnd => left & right,
+    };
+
+    check_bounds(result, span)
+}
+
+/// Check that an integer fits into 256 bits as either a signed or an unsigned
+/// value.
+fn check_bounds(val: BigInt, span: Span) -> Result<BigInt, ConstEvalError> {
+    let min = -(BigInt::from(1) << 255usize);
+    let max = (BigInt::from(1) << 256usize) - 1;
+
+    if val >= min && val <= max {
+        Ok(val)
+    } else {
+        Err(ConstEvalError::Overflow { span })
+    }
+}
+
+/// Parse an integer literal, which may have a radix prefix and separators.
+fn parse_int(literal: &str, span: Span) -> Result<BigInt, ConstEvalError> {
+    let digits = strip_separators(literal).to_ascii_lowercase();
+    let (digits, radix) = match digits.get(..2) {
+        Some("0x") => (&digits[2..], 16),
+        Some("0o") => (&digits[2..], 8),
+        Some("0b") => (&digits[2..], 2),
+        _ => (&digits[..], 10),
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(ConstEvalError::NotConstant { span });
+    }
+
+    // The digits are valid, so parsing can't fail
+    let val = BigInt::parse_bytes(digits.as_bytes(), radix).expect("invalid digits");
+    check_bounds(val, span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_parse_tokens;
+    use crate::parsers::expr;
+
+    fn eval_src(
+        src: &str,
+        env: &HashMap<String, ConstValue>,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let tokens = get_parse_tokens(src).unwrap();
+        let exp = expr(&tokens).unwrap().1;
+
+        eval_const(&exp, env)
+    }
+
+    fn int(val: i64) -> Result<ConstValue, ConstEvalError> {
+        Ok(ConstValue::Int(BigInt::from(val)))
+    }
+
+    #[test]
+    fn test_eval_const() {
+        let env = HashMap::new();
+
+        assert_eq!(eval_src("2 ** 8", &env), int(256));
+        assert_eq!(eval_src("(1 + 2) * 3", &env), int(9));
+        assert_eq!(eval_src("~0", &env), int(-1));
+        assert_eq!(eval_src("-7 // 2 + 0x10 % 3", &env), int(-2));
+        assert_eq!(eval_src("1_000 - 0b11", &env), int(997));
+        assert_eq!(eval_src("1 << 4 | 1", &env), int(17));
+        assert_eq!(eval_src("2 ** 255 // 2 ** 254", &env), int(2));
+        assert_eq!(eval_src("(-1) ** 1000000000001", &env), int(-1));
+        assert_eq!(eval_src("-8 >> 1000000000000", &env), int(-1));
+        assert_eq!(
+            eval_src("not (1 < 2) or 2 ** 8 == 256", &env),
+            Ok(ConstValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_eval_const_names() {
+        let mut env = HashMap::new();
+        env.insert("SIZE".to_string(), ConstValue::Int(BigInt::from(32)));
+
+        assert_eq!(eval_src("SIZE * 2", &env), int(64));
+        assert_eq!(
+            eval_src("SIZE + LEN", &env),
+            Err(ConstEvalError::UnknownName {
+                name: "LEN".to_string(),
+                span: Span::new(7, 10),
+            }),
+        );
+    }
+
+    #[test]
+    fn test_eval_const_errors() {
+        let env = HashMap::new();
+
+        let error = eval_src("1 + 4 / (2 - 2)", &env).unwrap_err();
+        assert_eq!(
+            error,
+            ConstEvalError::DivisionByZero {
+                span: Span::new(4, 15)
+            }
+        );
+        assert_eq!(error.message(), "division by zero");
+
+        assert_eq!(eval_src("2 ** 10", &env), int(1024));
+        assert_eq!(
+            eval_src("1 + 2 ** 256", &env),
+            Err(ConstEvalError::Overflow {
+                span: Span::new(6, 8)
+            }),
+        );
+        assert_eq!(
+            eval_src("1 + 3 ** 1000000000000", &env),
+            Err(ConstEvalError::Overflow {
+                span: Span::new(6, 8)
+            }),
+        );
+        assert_eq!(
+            eval_src("1 << 1000000000000", &env),
+            Err(ConstEvalError::Overflow {
+                span: Span::new(0, 18)
+            }),
+        );
+        assert_eq!(
+            eval_src(
+                "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff + 1",
+                &env
+            ),
+            Err(ConstEvalError::Overflow {
+                span: Span::new(0, 70)
+            }),
+        );
+        assert_eq!(
+            eval_src("2 * 1.5", &env),
+            Err(ConstEvalError::FloatLiteral {
+                span: Span::new(4, 7)
+            }),
+        );
+        assert_eq!(
+            eval_src("1e10", &env),
+            Err(ConstEvalError::FloatLiteral {
+                span: Span::new(0, 4)
+            }),
+        );
+        assert_eq!(
+            eval_src("1 + true", &env),
+            Err(ConstEvalError::TypeMismatch {
+                span: Span::new(0, 8)
+            }),
+        );
+        assert_eq!(
+            eval_src("f(1)", &env),
+            Err(ConstEvalError::NotConstant {
+                span: Span::new(0, 4)
+            }),
+        );
+    }
+}
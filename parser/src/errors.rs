@@ -1,4 +1,13 @@
-use crate::string_utils::StringPositions;
+use crate::parsers::current_span;
+use crate::span::Span;
+use crate::string_utils::{
+    lines_with_endings,
+    StringPositions,
+};
+use crate::tokenizer::{
+    TokenType,
+    TokenizeError,
+};
 use crate::Cursor;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -6,16 +15,50 @@ pub enum ErrorKind {
     StaticStr(&'static str),
     Str(String),
     Eof,
+    /// A token of type `found` appeared where one of the keywords in
+    /// `expected` was required.
+    UnexpectedToken {
+        found: TokenType,
+        expected: Vec<&'static str>,
+    },
 }
 
 impl ErrorKind {
-    pub fn description(&self) -> &str {
+    pub fn description(&self) -> String {
         use ErrorKind::*;
 
         match self {
-            StaticStr(s) => s,
-            Str(s) => s.as_str(),
-            Eof => "end of file",
+            StaticStr(s) => s.to_string(),
+            Str(s) => s.clone(),
+            Eof => "end of file".to_string(),
+            UnexpectedToken { found, expected } => {
+                let quoted: Vec<_> = expected.iter().map(|kw| format!("'{}'", kw)).collect();
+                let expected = match quoted.split_last() {
+                    Some((last, [])) => last.clone(),
+                    Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                    None => "nothing".to_string(),
+                };
+
+                format!("expected {}, found {}", expected, found.description())
+            }
+        }
+    }
+}
+
+/// A suggested fix for an error that replaces the source code at `span` with
+/// `replacement`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl Suggestion {
+    /// Create a suggestion to insert `text` at byte offset `offset`.
+    pub fn insert(offset: usize, text: &str) -> Self {
+        Self {
+            span: Span::new(offset, offset),
+            replacement: text.to_owned(),
         }
     }
 }
@@ -23,12 +66,14 @@ impl ErrorKind {
 #[derive(Debug, PartialEq, Clone)]
 pub struct ParseError<'a> {
     errors: Vec<(Cursor<'a>, ErrorKind)>,
+    suggestions: Vec<Suggestion>,
 }
 
 impl<'a> ParseError<'a> {
     pub fn new(input: Cursor<'a>, kind: ErrorKind) -> Self {
         Self {
             errors: vec![(input, kind)],
+            suggestions: vec![],
         }
     }
 
@@ -44,11 +89,47 @@ impl<'a> ParseError<'a> {
         Self::new(input, ErrorKind::Eof)
     }
 
+    /// Create an error for the unexpected token at the start of `input`.
+    pub fn unexpected_token(input: Cursor<'a>, expected: &[&'static str]) -> Self {
+        let found = input.first().map_or(TokenType::ENDMARKER, |tok| tok.typ);
+
+        Self::new(
+            input,
+            ErrorKind::UnexpectedToken {
+                found,
+                expected: expected.to_vec(),
+            },
+        )
+    }
+
     pub fn push(mut self, input: Cursor<'a>, kind: ErrorKind) -> Self {
         self.errors.push((input, kind));
         self
     }
 
+    /// Add a suggested fix to the error.
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// Suggested fixes for the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    /// The kind of the innermost error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.errors.first().unwrap().1
+    }
+
+    /// The number of tokens left unparsed at the innermost error.  Of two
+    /// errors from alternative parsers, the one with fewer remaining tokens
+    /// got further.
+    pub fn remaining_len(&self) -> usize {
+        self.errors.first().map_or(0, |(input, _)| input.len())
+    }
+
     /// Format an error into a debug trace message.
     #[cfg_attr(tarpaulin, rustfmt::skip)]
     pub fn format_debug(&self, input: &str, show_err_no: bool) -> String {
@@ -96,19 +177,68 @@ impl<'a> ParseError<'a> {
     /// Format an error into a user-facing error message.
     ///
     /// Uses the innermost error to build a user-facing error message and
-    /// position.
-    #[cfg_attr(tarpaulin, rustfmt::skip)]
+    /// position, rendered as by `render_error`.
     pub fn format_user(&self, input: &str) -> String {
-        let deepest_error = self.errors.first().unwrap();
+        render_error(input, &Diagnostic::from(self))
+    }
+}
 
-        let new_err = ParseError {
-            errors: vec![deepest_error.clone()],
-        };
+/// A problem found while tokenizing or parsing a source file.  Unlike
+/// `ParseError`, a diagnostic does not borrow the token stream.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+}
 
-        new_err.format_debug(input, false)
+impl From<&ParseError<'_>> for Diagnostic {
+    /// Build a diagnostic from the innermost error.
+    fn from(error: &ParseError) -> Self {
+        let (input, kind) = error.errors.first().unwrap();
+
+        Self {
+            message: kind.description(),
+            span: current_span(input),
+        }
     }
 }
 
+impl From<TokenizeError> for Diagnostic {
+    fn from(error: TokenizeError) -> Self {
+        Self {
+            message: error.msg.to_owned(),
+            span: Span::new(error.offset, error.offset),
+        }
+    }
+}
+
+/// Render `error` as a message followed by the offending line of `source`
+/// with the error's span underlined.  Spans that run past the end of their
+/// first line are underlined to the end of that line.
+pub fn render_error(source: &str, error: &Diagnostic) -> String {
+    let ((line_no, col), (end_line_no, end_col)) = error.span.line_col(source);
+
+    let line = lines_with_endings(source)
+        .nth(line_no - 1)
+        .map_or("", |(line, _, _)| line.trim_end_matches(&['\r', '\n'][..]));
+    let end_col = if end_line_no == line_no {
+        end_col
+    } else {
+        line.chars().count()
+    };
+    let width = end_col.saturating_sub(col).max(1);
+
+    format!(
+        "at line {} col {}, {}:\n{}\n{}{}\n",
+        line_no,
+        col,
+        error.message,
+        line,
+        " ".repeat(col),
+        "^".repeat(width),
+    )
+}
+
 #[cfg_attr(tarpaulin, rustfmt::skip)]
 #[cfg(test)]
 mod tests {
@@ -127,6 +257,14 @@ mod tests {
         assert_eq!(Str("foo".to_string()).description(), "foo");
         assert_eq!(StaticStr("foo").description(), "foo");
         assert_eq!(Eof.description(), "end of file");
+        assert_eq!(
+            UnexpectedToken {
+                found: TokenType::STRING,
+                expected: vec!["contract", "import"],
+            }
+            .description(),
+            "expected 'contract' or 'import', found string literal",
+        );
     }
 
     #[test]
@@ -135,18 +273,21 @@ mod tests {
             ParseError::str(empty_slice!(), "foo"),
             ParseError {
                 errors: vec![(empty_slice!(), Str("foo".to_string()))],
+                suggestions: vec![],
             }
         );
         assert_eq!(
             ParseError::static_str(empty_slice!(), "foo"),
             ParseError {
                 errors: vec![(empty_slice!(), StaticStr("foo"))],
+                suggestions: vec![],
             }
         );
         assert_eq!(
             ParseError::eof(empty_slice!()),
             ParseError {
                 errors: vec![(empty_slice!(), Eof)],
+                suggestions: vec![],
             }
         );
     }
@@ -165,6 +306,7 @@ mod tests {
             err.push(&toks[..], StaticStr("some other error")),
             ParseError {
                 errors: vec![(tok_eof, Eof), (&toks[..], StaticStr("some other error")),],
+                suggestions: vec![],
             }
         );
     }
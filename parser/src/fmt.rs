@@ -0,0 +1,921 @@
+//! Render a module tree back into Fe source code.
+//!
+//! The output is canonically formatted: statements are indented by four
+//! spaces, definitions are separated by blank lines, and lists of names
+//! imported with "from" are parenthesized when there is more than one.
+//! Expressions are only parenthesized where their precedence requires it.
+//! Inline Yul blocks cannot be rendered, since their code is not part of the
+//! tree.
+
+use crate::ast::*;
+use crate::errors::Diagnostic;
+use crate::span::Spanned;
+
+const INDENT: &str = "    ";
+
+// The precedence of each kind of expression, from loosest to tightest binding.
+const TERNARY: u8 = 1;
+const OR: u8 = 2;
+const AND: u8 = 3;
+const NOT: u8 = 4;
+const COMPARISON: u8 = 5;
+const BIT_OR: u8 = 6;
+const BIT_XOR: u8 = 7;
+const BIT_AND: u8 = 8;
+const SHIFT: u8 = 9;
+const SUM: u8 = 10;
+const TERM: u8 = 11;
+const CAST: u8 = 12;
+const UNARY: u8 = 13;
+const POWER: u8 = 14;
+const PRIMARY: u8 = 15;
+
+/// Render `module` as Fe source code.  Returns an error for inline Yul
+/// blocks, whose code can't be recovered from the tree.
+pub fn to_source(module: &Spanned<Module>) -> Result<String, Diagnostic> {
+    let mut result = String::new();
+    let mut prev: Option<&ModuleStmt> = None;
+
+    for stmt in module.node.body.iter() {
+        if let Some(prev) = prev {
+            if !(is_import(prev) && is_import(&stmt.node)) {
+                result.push('\n');
+            }
+        }
+
+        module_stmt(&mut result, &stmt.node)?;
+        prev = Some(&stmt.node);
+    }
+
+    Ok(result)
+}
+
+fn is_import(stmt: &ModuleStmt) -> bool {
+    matches!(
+        stmt,
+        ModuleStmt::SimpleImport { .. } | ModuleStmt::FromImport { .. } | ModuleStmt::Export { .. }
+    )
+}
+
+fn module_stmt(out: &mut String, stmt: &ModuleStmt) -> Result<(), Diagnostic> {
+    match stmt {
+        ModuleStmt::TypeDef { name, typ } => line(
+            out,
+            0,
+            &format!("type {} = {}", name.node, type_desc(&typ.node)),
+        ),
+        ModuleStmt::SimpleImport { names } => {
+            let names: Vec<_> = names
+                .iter()
+                .map(|name| simple_import_name(&name.node))
+                .collect();
+
+            line(out, 0, &format!("import {}", names.join(", ")))
+        }
+        ModuleStmt::FromImport { path, names } => line(
+            out,
+            0,
+            &format!(
+                "from {} import {}",
+                from_import_path(&path.node),
+                from_import_names(&names.node),
+            ),
+        ),
+        ModuleStmt::Export { name } => {
+            let name = match &name.node {
+                ExportName::Star => "*".to_string(),
+                ExportName::Path(path) => simple_import_name(path),
+            };
+
+            line(out, 0, &format!("export {}", name))
+        }
+        ModuleStmt::ContractDef {
+            name,
+            base,
+            body,
+            pub_,
+        } => {
+            let pub_ = if *pub_ { "pub " } else { "" };
+            let base = match base.len() {
+                0 => String::new(),
+                _ => {
+                    let names: Vec<_> = base.iter().map(|base| base.node).collect();
+                    format!("({})", names.join(", "))
+                }
+            };
+            line(out, 0, &format!("{}contract {}{}:", pub_, name.node, base));
+
+            for stmt in body.iter() {
+                contract_stmt(out, 1, &stmt.node)?;
+            }
+        }
+        ModuleStmt::StructDef {
+            name,
+            body,
+            is_marker,
+        } => struct_def(out, 0, name.node, body, *is_marker),
+        ModuleStmt::InterfaceDef { name, functions } => {
+            line(out, 0, &format!("interface {}:", name.node));
+
+            for function in functions.iter() {
+                line(out, 1, &func_sig(&function.node));
+            }
+        }
+        ModuleStmt::Docstring { lines } => line(out, 0, &strings(lines)),
+        ModuleStmt::ConstDef { name, typ, value } => line(
+            out,
+            0,
+            &format!(
+                "const {}: {} = {}",
+                name.node,
+                type_desc(&typ.node),
+                expr(&value.node)
+            ),
+        ),
+        ModuleStmt::StaticAssert { cond, msg } => line(out, 0, &static_assert(cond, msg)),
+    }
+
+    Ok(())
+}
+
+fn contract_stmt(out: &mut String, depth: usize, stmt: &ContractStmt) -> Result<(), Diagnostic> {
+    match stmt {
+        ContractStmt::ContractField { qual, name, typ } => {
+            let qual = match qual.as_ref().map(|qual| &qual.node) {
+                Some(ContractFieldQual::Const) => "const ",
+                Some(ContractFieldQual::Pub) => "pub ",
+                None => "",
+            };
+
+            line(
+                out,
+                depth,
+                &format!("{}{}: {}", qual, name.node, type_desc(&typ.node)),
+            )
+        }
+        ContractStmt::EventDef {
+            name,
+            fields,
+            anonymous,
+            doc,
+        } => {
+            let anonymous = if *anonymous { " anonymous" } else { "" };
+            line(out, depth, &format!("event {}{}:", name.node, anonymous));
+
+            if let Some(lines) = doc {
+                line(out, depth + 1, &strings(lines));
+            }
+
+            for field in fields.iter() {
+                line(out, depth + 1, &event_field(&field.node));
+            }
+        }
+        ContractStmt::StructDef {
+            name,
+            body,
+            is_marker,
+        } => struct_def(out, depth, name.node, body, *is_marker),
+        ContractStmt::Docstring { lines } => line(out, depth, &strings(lines)),
+        ContractStmt::PackedGroup { fields } => {
+            line(out, depth, "packed:");
+
+            for field in fields.iter() {
+                contract_stmt(out, depth + 1, &field.node)?;
+            }
+        }
+        ContractStmt::FuncDef {
+            decorators,
+            qual,
+            name,
+            generic_params,
+            args,
+            return_type,
+            named_returns,
+            body,
+            is_constructor: _,
+            init_only,
+        } => {
+            for decorator in decorators.iter() {
+                line(out, depth, &format!("@{}", decorator.node));
+            }
+
+            let pub_ = if qual.is_some() { "pub " } else { "" };
+            let init = if *init_only { "init " } else { "" };
+            let generic_params = match generic_params.len() {
+                0 => String::new(),
+                _ => {
+                    let params: Vec<_> = generic_params
+                        .iter()
+                        .map(|param| generic_param(&param.node))
+                        .collect();
+                    format!("<{}>", params.join(", "))
+                }
+            };
+            let return_type = match (named_returns.len(), return_type) {
+                (0, Some(typ)) => format!(" -> {}", type_desc(&typ.node)),
+                (0, None) => String::new(),
+                _ => format!(" -> ({})", func_def_args(named_returns)),
+            };
+
+            line(
+                out,
+                depth,
+                &format!(
+                    "{}{}def {}{}({}){}:",
+                    pub_,
+                    init,
+                    name.node,
+                    generic_params,
+                    func_def_args(args),
+                    return_type,
+                ),
+            );
+            block(out, depth + 1, body)?;
+        }
+        ContractStmt::StaticAssert { cond, msg } => line(out, depth, &static_assert(cond, msg)),
+    }
+
+    Ok(())
+}
+
+fn struct_def(
+    out: &mut String,
+    depth: usize,
+    name: &str,
+    body: &[Spanned<StructStmt>],
+    is_marker: bool,
+) {
+    if is_marker {
+        return line(out, depth, &format!("struct {}", name));
+    }
+
+    line(out, depth, &format!("struct {}:", name));
+
+    for stmt in body.iter() {
+        let StructStmt::StructField { qual, name, typ } = &stmt.node;
+        let qual = match qual.as_ref().map(|qual| &qual.node) {
+            Some(StructFieldQual::Const) => "const ",
+            Some(StructFieldQual::Pub) => "pub ",
+            None => "",
+        };
+
+        line(
+            out,
+            depth + 1,
+            &format!("{}{}: {}", qual, name.node, type_desc(&typ.node)),
+        );
+    }
+}
+
+fn event_field(field: &EventField) -> String {
+    let qual = match field.qual.as_ref().map(|qual| &qual.node) {
+        Some(EventFieldQual::Idx) => "idx ",
+        None => "",
+    };
+
+    format!(
+        "{}{}: {}",
+        qual,
+        field.name.node,
+        type_desc(&field.typ.node)
+    )
+}
+
+fn func_sig(sig: &FuncSig) -> String {
+    let return_type = match &sig.return_type {
+        Some(typ) => format!(" -> {}", type_desc(&typ.node)),
+        None => String::new(),
+    };
+
+    format!(
+        "def {}({}){}",
+        sig.name.node,
+        func_def_args(&sig.args),
+        return_type
+    )
+}
+
+fn func_def_args(args: &[Spanned<FuncDefArg>]) -> String {
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| format!("{}: {}", arg.node.name.node, type_desc(&arg.node.typ.node)))
+        .collect();
+
+    args.join(", ")
+}
+
+fn generic_param(param: &GenericParameter) -> String {
+    match param {
+        GenericParameter::Type { name } => name.node.to_string(),
+        GenericParameter::Const { name, typ } => {
+            format!("const {}: {}", name.node, type_desc(&typ.node))
+        }
+    }
+}
+
+fn static_assert(cond: &Spanned<Expr>, msg: &Option<Spanned<Vec<&str>>>) -> String {
+    match msg {
+        Some(msg) => format!(
+            "static_assert({}, {})",
+            expr(&cond.node),
+            strings(&msg.node)
+        ),
+        None => format!("static_assert({})", expr(&cond.node)),
+    }
+}
+
+fn block(out: &mut String, depth: usize, body: &[Spanned<FuncStmt>]) -> Result<(), Diagnostic> {
+    for stmt in body.iter() {
+        func_stmt(out, depth, &stmt.node)?;
+    }
+
+    Ok(())
+}
+
+fn func_stmt(out: &mut String, depth: usize, stmt: &FuncStmt) -> Result<(), Diagnostic> {
+    match stmt {
+        FuncStmt::Return { value } => match value {
+            Some(value) => line(out, depth, &format!("return {}", expr(&value.node))),
+            None => line(out, depth, "return"),
+        },
+        FuncStmt::VarDecl { target, typ, value } => {
+            let value = match value {
+                Some(value) => format!(" = {}", expr(&value.node)),
+                None => String::new(),
+            };
+
+            line(
+                out,
+                depth,
+                &format!("{}: {}{}", expr(&target.node), type_desc(&typ.node), value),
+            )
+        }
+        FuncStmt::Assign { targets, value } => {
+            let mut text = String::new();
+            for target in targets.iter() {
+                text.push_str(&format!("{} = ", expr(&target.node)));
+            }
+            text.push_str(&expr(&value.node));
+
+            line(out, depth, &text)
+        }
+        FuncStmt::AugAssign { target, op, value } => line(
+            out,
+            depth,
+            &format!(
+                "{} {}= {}",
+                expr(&target.node),
+                bin_operator(&op.node),
+                expr(&value.node)
+            ),
+        ),
+        FuncStmt::For {
+            target,
+            iter,
+            body,
+            or_else,
+        } => {
+            line(
+                out,
+                depth,
+                &format!("for {} in {}:", expr(&target.node), expr(&iter.node)),
+            );
+            block(out, depth + 1, body)?;
+            else_block(out, depth, or_else)?;
+        }
+        FuncStmt::While {
+            test,
+            body,
+            or_else,
+        } => {
+            line(out, depth, &format!("while {}:", expr(&test.node)));
+            block(out, depth + 1, body)?;
+            else_block(out, depth, or_else)?;
+        }
+        FuncStmt::If {
+            test,
+            body,
+            or_else,
+        } => if_stmt(out, depth, "if", test, body, or_else)?,
+        FuncStmt::Unchecked { body } => {
+            line(out, depth, "unchecked:");
+            block(out, depth + 1, body)?;
+        }
+        FuncStmt::Yul { source_span } => {
+            return Err(Diagnostic {
+                message: "inline Yul blocks cannot be rendered".to_string(),
+                span: *source_span,
+            })
+        }
+        FuncStmt::Assert { test, msg } => match msg {
+            Some(msg) => line(
+                out,
+                depth,
+                &format!("assert {}, {}", expr(&test.node), expr(&msg.node)),
+            ),
+            None => line(out, depth, &format!("assert {}", expr(&test.node))),
+        },
+        FuncStmt::Emit { value } => line(out, depth, &format!("emit {}", expr(&value.node))),
+        FuncStmt::Expr { value } => line(out, depth, &expr(value)),
+        FuncStmt::Pass => line(out, depth, "pass"),
+        FuncStmt::Break => line(out, depth, "break"),
+        FuncStmt::Continue => line(out, depth, "continue"),
+        FuncStmt::Revert => line(out, depth, "revert"),
+    }
+
+    Ok(())
+}
+
+/// Render an if statement.  An else block that only contains another if
+/// statement is rendered as "elif".
+fn if_stmt(
+    out: &mut String,
+    depth: usize,
+    keyword: &str,
+    test: &Spanned<Expr>,
+    body: &[Spanned<FuncStmt>],
+    or_else: &[Spanned<FuncStmt>],
+) -> Result<(), Diagnostic> {
+    line(out, depth, &format!("{} {}:", keyword, expr(&test.node)));
+    block(out, depth + 1, body)?;
+
+    match or_else {
+        [Spanned {
+            node:
+                FuncStmt::If {
+                    test,
+                    body,
+                    or_else,
+                },
+            ..
+        }] => if_stmt(out, depth, "elif", test, body, or_else),
+        _ => else_block(out, depth, or_else),
+    }
+}
+
+fn else_block(
+    out: &mut String,
+    depth: usize,
+    or_else: &[Spanned<FuncStmt>],
+) -> Result<(), Diagnostic> {
+    if or_else.is_empty() {
+        return Ok(());
+    }
+
+    line(out, depth, "else:");
+    block(out, depth + 1, or_else)
+}
+
+/// Render an expression, adding the parentheses needed to parse it back into
+/// the same tree.
+fn expr(exp: &Expr) -> String {
+    match exp {
+        Expr::Ternary {
+            if_expr,
+            test,
+            else_expr,
+        } => format!(
+            "{} if {} else {}",
+            operand(if_expr, OR),
+            operand(test, OR),
+            operand(else_expr, TERNARY)
+        ),
+        Expr::BoolOperation { left, op, right } => {
+            let prec = precedence(exp);
+
+            format!(
+                "{} {} {}",
+                operand(left, prec),
+                bool_operator(&op.node),
+                operand(right, prec + 1)
+            )
+        }
+        Expr::BinOperation { left, op, right } if op.node == BinOperator::Pow => {
+            format!("{} ** {}", operand(left, PRIMARY), operand(right, UNARY))
+        }
+        Expr::BinOperation { left, op, right } => {
+            let prec = precedence(exp);
+
+            format!(
+                "{} {} {}",
+                operand(left, prec),
+                bin_operator(&op.node),
+                operand(right, prec + 1)
+            )
+        }
+        Expr::UnaryOperation { op, operand: value } => match op.node {
+            UnaryOperator::Not => format!("not {}", operand(value, NOT)),
+            UnaryOperator::Invert => format!("~{}", operand(value, UNARY)),
+            UnaryOperator::UAdd => format!("+{}", operand(value, UNARY)),
+            UnaryOperator::USub => format!("-{}", operand(value, UNARY)),
+        },
+        Expr::CompOperation { left, op, right } => format!(
+            "{} {} {}",
+            operand(left, COMPARISON),
+            comp_operator(&op.node),
+            operand(right, COMPARISON + 1)
+        ),
+        Expr::Attribute { value, attr } => format!("{}.{}", operand(value, PRIMARY), attr.node),
+        Expr::Subscript { value, slices } => {
+            let slices: Vec<_> = slices.node.iter().map(|item| slice(&item.node)).collect();
+
+            format!("{}[{}]", operand(value, PRIMARY), slices.join(", "))
+        }
+        Expr::Cast { value, typ, mode } => {
+            let as_ = match mode {
+                CastMode::Checked => "as",
+                CastMode::ExplicitChecked => "as?",
+                CastMode::Unchecked => "as!",
+            };
+
+            format!("{} {} {}", operand(value, CAST), as_, type_desc(&typ.node))
+        }
+        Expr::Call {
+            func,
+            call_modifiers,
+            args,
+        } => {
+            let call_modifiers = match call_modifiers.len() {
+                0 => String::new(),
+                _ => {
+                    let modifiers: Vec<_> = call_modifiers
+                        .iter()
+                        .map(|modifier| {
+                            format!(
+                                "{}: {}",
+                                modifier.node.name.node,
+                                expr(&modifier.node.value.node)
+                            )
+                        })
+                        .collect();
+                    format!("{{{}}}", modifiers.join(", "))
+                }
+            };
+            let args: Vec<_> = args
+                .node
+                .iter()
+                .map(|arg| match &arg.node {
+                    CallArg::Arg(value) => expr(value),
+                    CallArg::Kwarg(Kwarg { name, value }) => {
+                        format!("{}={}", name.node, expr(&value.node))
+                    }
+                })
+                .collect();
+
+            format!(
+                "{}{}({})",
+                operand(func, PRIMARY),
+                call_modifiers,
+                args.join(", ")
+            )
+        }
+        Expr::List { elts } => format!("[{}]", exprs(elts)),
+        Expr::AnnotatedList { elts, typ } => {
+            format!("[{}]: {}", exprs(elts), type_desc(&typ.node))
+        }
+        Expr::ArrayRepeat { value, count } => {
+            format!("[{}; {}]", expr(&value.node), expr(&count.node))
+        }
+        Expr::Comptime { inner } => format!("comptime({})", expr(&inner.node)),
+        Expr::ListComp { elt, comps } => {
+            let mut text = format!("[{}", expr(&elt.node));
+            for comp in comps.iter() {
+                text.push_str(&format!(
+                    " for {} in {}",
+                    expr(&comp.node.target.node),
+                    expr(&comp.node.iter.node)
+                ));
+                for cond in comp.node.ifs.iter() {
+                    text.push_str(&format!(" if {}", expr(&cond.node)));
+                }
+            }
+            text.push(']');
+
+            text
+        }
+        Expr::Tuple { elts } => match elts.len() {
+            1 => format!("({},)", exprs(elts)),
+            _ => format!("({})", exprs(elts)),
+        },
+        Expr::Bool(true) => "true".to_string(),
+        Expr::Bool(false) => "false".to_string(),
+        Expr::Name(name) => name.to_string(),
+        Expr::Num { value, .. } => value.to_string(),
+        Expr::Str(lines) => strings(lines),
+        Expr::Ellipsis => "...".to_string(),
+        Expr::OperatorRef(operator) => {
+            let operator = match operator {
+                Operator::Bin(op) => bin_operator(op),
+                Operator::Bool(op) => bool_operator(op),
+                Operator::Comp(op) => comp_operator(op),
+            };
+
+            format!("({})", operator)
+        }
+    }
+}
+
+/// Render an operand that must bind at least as tightly as `min_prec`,
+/// parenthesizing it otherwise.
+fn operand(exp: &Spanned<Expr>, min_prec: u8) -> String {
+    if precedence(&exp.node) < min_prec {
+        format!("({})", expr(&exp.node))
+    } else {
+        expr(&exp.node)
+    }
+}
+
+fn precedence(exp: &Expr) -> u8 {
+    match exp {
+        Expr::Ternary { .. } => TERNARY,
+        Expr::BoolOperation { op, .. } => match op.node {
+            BoolOperator::Or => OR,
+            BoolOperator::And => AND,
+        },
+        Expr::UnaryOperation { op, .. } if op.node == UnaryOperator::Not => NOT,
+        Expr::UnaryOperation { .. } => UNARY,
+        Expr::CompOperation { .. } => COMPARISON,
+        Expr::BinOperation { op, .. } => match op.node {
+            BinOperator::BitOr => BIT_OR,
+            BinOperator::BitXor => BIT_XOR,
+            BinOperator::BitAnd => BIT_AND,
+            BinOperator::LShift | BinOperator::RShift => SHIFT,
+            BinOperator::Add
+            | BinOperator::Sub
+            | BinOperator::WrappingAdd
+            | BinOperator::WrappingSub => SUM,
+            BinOperator::Mult
+            | BinOperator::Div
+            | BinOperator::FloorDiv
+            | BinOperator::Mod
+            | BinOperator::WrappingMult => TERM,
+            BinOperator::Pow => POWER,
+        },
+        Expr::Cast { .. } => CAST,
+        // The trailing type of an annotated list would swallow any subscript
+        // that follows it
+        Expr::AnnotatedList { .. } => POWER,
+        _ => PRIMARY,
+    }
+}
+
+fn exprs(elts: &[Spanned<Expr>]) -> String {
+    let elts: Vec<_> = elts.iter().map(|elt| expr(&elt.node)).collect();
+
+    elts.join(", ")
+}
+
+fn slice(item: &Slice) -> String {
+    match item {
+        Slice::Index(index) => expr(index),
+        Slice::Slice { lower, upper, step } => {
+            let bound = |value: &Option<Box<Spanned<Expr>>>| match value {
+                Some(value) => expr(&value.node),
+                None => String::new(),
+            };
+
+            match step {
+                Some(step) => format!("{}:{}:{}", bound(lower), bound(upper), expr(&step.node)),
+                None => format!("{}:{}", bound(lower), bound(upper)),
+            }
+        }
+    }
+}
+
+fn bool_operator(op: &BoolOperator) -> &'static str {
+    match op {
+        BoolOperator::And => "and",
+        BoolOperator::Or => "or",
+    }
+}
+
+fn bin_operator(op: &BinOperator) -> &'static str {
+    match op {
+        BinOperator::Add => "+",
+        BinOperator::Sub => "-",
+        BinOperator::Mult => "*",
+        BinOperator::Div => "/",
+        BinOperator::Mod => "%",
+        BinOperator::Pow => "**",
+        BinOperator::LShift => "<<",
+        BinOperator::RShift => ">>",
+        BinOperator::BitOr => "|",
+        BinOperator::BitXor => "^",
+        BinOperator::BitAnd => "&",
+        BinOperator::FloorDiv => "//",
+        BinOperator::WrappingAdd => "+%",
+        BinOperator::WrappingSub => "-%",
+        BinOperator::WrappingMult => "*%",
+    }
+}
+
+fn comp_operator(op: &CompOperator) -> &'static str {
+    match op {
+        CompOperator::Eq => "==",
+        CompOperator::NotEq => "!=",
+        CompOperator::Lt => "<",
+        CompOperator::LtE => "<=",
+        CompOperator::Gt => ">",
+        CompOperator::GtE => ">=",
+        CompOperator::Is => "is",
+        CompOperator::IsNot => "is not",
+        CompOperator::In => "in",
+        CompOperator::NotIn => "not in",
+    }
+}
+
+/// Render adjacent string literals, whose quotes were stripped by the parser.
+fn strings(lines: &[&str]) -> String {
+    let literals: Vec<_> = lines
+        .iter()
+        .map(|string| {
+            if string.contains('\n') {
+                format!("\"\"\"{}\"\"\"", string)
+            } else if string.contains('"') {
+                format!("'{}'", string)
+            } else {
+                format!("\"{}\"", string)
+            }
+        })
+        .collect();
+
+    literals.join(" ")
+}
+
+fn simple_import_name(name: &SimpleImportName) -> String {
+    let path: Vec<_> = name.path.iter().map(|part| part.node).collect();
+
+    match &name.alias {
+        Some(alias) => format!("{} as {}", path.join("."), alias.node),
+        None => path.join("."),
+    }
+}
+
+fn from_import_path(path: &FromImportPath) -> String {
+    let (dots, path) = match path {
+        FromImportPath::Absolute { path } => (0, path),
+        FromImportPath::Relative { dots, path } => (*dots, path),
+    };
+    let path: Vec<_> = path.iter().map(|part| part.node).collect();
+
+    format!("{}{}", ".".repeat(dots), path.join("."))
+}
+
+fn from_import_names(names: &FromImportNames) -> String {
+    let names = match names {
+        FromImportNames::Star => return "*".to_string(),
+        FromImportNames::List(names) => names,
+    };
+    let names: Vec<_> = names
+        .iter()
+        .map(|name| match &name.node.alias {
+            Some(alias) => format!("{} as {}", name.node.name.node, alias.node),
+            None => name.node.name.node.to_string(),
+        })
+        .collect();
+
+    if names.len() == 1 {
+        names[0].clone()
+    } else {
+        format!("({})", names.join(", "))
+    }
+}
+
+/// Render a type description e.g. "map<address, u256[10]>".
+pub fn type_desc(typ: &TypeDesc) -> String {
+    match typ {
+        TypeDesc::Base { base } => base.to_string(),
+        TypeDesc::Array { typ, dimension } => format!("{}[{}]", type_desc(&typ.node), dimension),
+        TypeDesc::Int { signed, bits } => {
+            format!("{}<{}>", if *signed { "int" } else { "uint" }, bits)
+        }
+        TypeDesc::GenericArray { typ, dimension } => {
+            format!("{}[{}]", type_desc(&typ.node), dimension)
+        }
+        TypeDesc::Map { from, to } => {
+            format!("map<{}, {}>", type_desc(&from.node), type_desc(&to.node))
+        }
+        TypeDesc::Tuple { items } => {
+            let items: Vec<_> = items.iter().map(|item| type_desc(&item.node)).collect();
+
+            match items.len() {
+                1 => format!("({},)", items[0]),
+                _ => format!("({})", items.join(", ")),
+            }
+        }
+        TypeDesc::Never => "never".to_string(),
+    }
+}
+
+fn line(out: &mut String, depth: usize, text: &str) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+
+    out.push_str(text);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Diagnostic;
+    use crate::fmt::to_source;
+    use crate::parse_module;
+    use crate::span::Span;
+
+    #[test]
+    fn test_to_source_round_trip() {
+        let src = "\
+import foo.bar as baz, qux
+from . import a
+from ..parent.mod import (b as c, d)
+from lib import *
+export Token
+
+type Balances = map<address, u256>
+
+struct Point:
+    pub x: uint<24>
+    y: (u8, bool)
+
+struct Marker
+
+interface Ownable:
+    def owner() -> address
+    def transfer_ownership(new_owner: address, notify: bool)
+
+const SIZE: u256 = (1 + 2) * 3 ** -x
+
+static_assert(SIZE < 32, \"too\" \"big\")
+
+pub contract Foo(Ownable, Pausable):
+    const max: u256[10][2]
+    packed:
+        paused: bool
+        version: u8
+    event Transfer anonymous:
+        idx sender: address
+        value: u256
+    struct Inner:
+        x: (u256,)
+    static_assert(not (a or b) and c)
+    @payable
+    pub def sum<T, const N: u256>(values: u256[N]) -> (total: u256, ok: bool):
+        \"Add up values.\"
+        total: u256 = 0
+        for value in values:
+            total += value
+        else:
+            pass
+        a = b = -(x - y) // (x - y) if x > y else [1, 2][0]
+        c: (u256, bool) = (x.y[1:2, ::3], not x in y)
+        assert self.balances[msg.sender] >= x as! u8, \"balance\"
+        d = x as? i8 + y as u8
+        return (total, true)
+    init def __init__():
+        if x == 1:
+            emit Transfer(sender=msg.sender, value=comptime(2 ** 8))
+        elif x is not y:
+            Foo(addr).bar{value: 1}((+), [0; 4], ...)
+        else:
+            unchecked:
+                revert
+        while true:
+            break
+    def baz() -> never:
+        x = ((a, b), (c,))
+        continue
+";
+        let module = parse_module(src).unwrap();
+        let rendered = to_source(&module).unwrap();
+        assert_eq!(rendered, src);
+
+        assert_eq!(parse_module(&rendered), Ok(module));
+    }
+
+    #[test]
+    fn test_to_source_normalizes() {
+        let src =
+            "from foo import (bar)\ncontract   Foo:\n  x :   u256\n\n\n  event E:\n     x: bool\n";
+        let module = parse_module(src).unwrap();
+
+        assert_eq!(
+            to_source(&module).unwrap(),
+            "from foo import bar\n\ncontract Foo:\n    x: u256\n    event E:\n        x: bool\n",
+        );
+    }
+
+    #[test]
+    fn test_to_source_yul_err() {
+        let src = "contract Foo:\n    def bar():\n        yul:\n            sstore(0, 1)\n";
+        let module = parse_module(src).unwrap();
+
+        assert_eq!(
+            to_source(&module),
+            Err(Diagnostic {
+                message: "inline Yul blocks cannot be rendered".to_string(),
+                span: Span::new(54, 66),
+            }),
+        );
+    }
+}
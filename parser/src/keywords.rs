@@ -0,0 +1,180 @@
+use crate::span::Span;
+use crate::tokenizer::types::{
+    Token,
+    TokenType,
+};
+
+/// Words with a fixed meaning in the grammar.  These may never be used as
+/// identifiers.
+pub const HARD_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "break", "continue", "elif", "else", "false", "for", "if", "in", "is",
+    "not", "or", "return", "revert", "true", "while",
+];
+
+/// Words that only have a meaning in specific positions e.g. "from" in an
+/// import statement.  Elsewhere they may be used as identifiers.
+pub const SOFT_KEYWORDS: &[&str] = &[
+    "const",
+    "contract",
+    "def",
+    "emit",
+    "event",
+    "export",
+    "from",
+    "import",
+    "pass",
+    "pub",
+    "struct",
+    "type",
+    "unchecked",
+];
+
+/// Words that are not yet keywords but may become keywords in a later version
+/// of the language.  Using them as identifiers produces a warning.
+pub const RESERVED_WORDS: &[&str] = &[
+    "async", "await", "enum", "impl", "let", "loop", "match", "mut", "trait", "use", "yield",
+];
+
+/// The tier to which a keyword belongs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum KeywordTier {
+    /// A keyword that is an error to use as an identifier.
+    Hard,
+    /// A keyword only in specific positions that may also be used as an
+    /// identifier.
+    Soft,
+    /// A word reserved for future use that is a warning to use as an
+    /// identifier.
+    Reserved,
+}
+
+/// A warning produced when a reserved word is used as an identifier.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReservedWordWarning<'a> {
+    pub word: &'a str,
+    pub span: Span,
+}
+
+impl<'a> ReservedWordWarning<'a> {
+    pub fn message(&self) -> String {
+        format!(
+            "\"{}\" is reserved for future use and should not be used as an identifier",
+            self.word,
+        )
+    }
+}
+
+/// The set of keywords recognized by the language, split into tiers.
+///
+/// The parser always rejects identifiers found in [`HARD_KEYWORDS`] and
+/// accepts those found in [`SOFT_KEYWORDS`].  The reserved tier can be extended
+/// to warn users off names that are expected to become keywords.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Keywords {
+    hard: Vec<&'static str>,
+    soft: Vec<&'static str>,
+    reserved: Vec<&'static str>,
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        Self {
+            hard: HARD_KEYWORDS.to_vec(),
+            soft: SOFT_KEYWORDS.to_vec(),
+            reserved: RESERVED_WORDS.to_vec(),
+        }
+    }
+}
+
+impl Keywords {
+    /// Add words to the reserved tier.  Words that are already keywords are
+    /// ignored.
+    pub fn with_reserved(mut self, words: &[&'static str]) -> Self {
+        for word in words {
+            if self.tier(word).is_none() {
+                self.reserved.push(word);
+            }
+        }
+        self
+    }
+
+    /// Return the tier of the given word or `None` if it is not a keyword.
+    pub fn tier(&self, word: &str) -> Option<KeywordTier> {
+        if self.hard.contains(&word) {
+            Some(KeywordTier::Hard)
+        } else if self.soft.contains(&word) {
+            Some(KeywordTier::Soft)
+        } else if self.reserved.contains(&word) {
+            Some(KeywordTier::Reserved)
+        } else {
+            None
+        }
+    }
+
+    /// Return a warning for every name token in `tokens` that contains a
+    /// reserved word.  Reserved words are never keywords, so any name token
+    /// containing one is an identifier.
+    pub fn reserved_warnings<'a>(&self, tokens: &[Token<'a>]) -> Vec<ReservedWordWarning<'a>> {
+        tokens
+            .iter()
+            .filter(|tok| {
+                tok.typ == TokenType::NAME && self.tier(tok.string) == Some(KeywordTier::Reserved)
+            })
+            .map(|tok| ReservedWordWarning {
+                word: tok.string,
+                span: tok.span,
+            })
+            .collect()
+    }
+}
+
+/// Return true if `word` is a hard keyword.
+pub fn is_hard_keyword(word: &str) -> bool {
+    HARD_KEYWORDS.contains(&word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_parse_tokens;
+
+    #[test]
+    fn test_tier() {
+        let keywords = Keywords::default();
+
+        assert_eq!(keywords.tier("while"), Some(KeywordTier::Hard));
+        assert_eq!(keywords.tier("from"), Some(KeywordTier::Soft));
+        assert_eq!(keywords.tier("match"), Some(KeywordTier::Reserved));
+        assert_eq!(keywords.tier("foo"), None);
+
+        let keywords = keywords.with_reserved(&["foo", "while", "from"]);
+
+        assert_eq!(keywords.tier("foo"), Some(KeywordTier::Reserved));
+        assert_eq!(keywords.tier("while"), Some(KeywordTier::Hard));
+        assert_eq!(keywords.tier("from"), Some(KeywordTier::Soft));
+    }
+
+    #[test]
+    fn test_reserved_warnings() {
+        let toks = get_parse_tokens("match = trait + x").unwrap();
+        let warnings = Keywords::default().reserved_warnings(&toks);
+
+        assert_eq!(
+            warnings,
+            vec![
+                ReservedWordWarning {
+                    word: "match",
+                    span: Span::new(0, 5),
+                },
+                ReservedWordWarning {
+                    word: "trait",
+                    span: Span::new(8, 13),
+                },
+            ],
+        );
+        assert_eq!(
+            warnings[0].message(),
+            "\"match\" is reserved for future use and should not be used as an identifier",
+        );
+    }
+}
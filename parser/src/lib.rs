@@ -2,7 +2,11 @@ extern crate regex;
 
 pub mod ast;
 pub mod builders;
+pub mod const_eval;
 pub mod errors;
+pub mod fmt;
+pub mod keywords;
+pub mod numeric;
 pub mod parsers;
 pub mod span;
 pub mod string_utils;
@@ -10,10 +14,22 @@ pub mod tokenizer;
 
 mod ast_traits;
 
-use errors::ParseError;
+use std::collections::HashMap;
+
+use ast::{
+    Module,
+    ModuleStmt,
+};
+use errors::{
+    Diagnostic,
+    ParseError,
+};
+use span::Spanned;
 use tokenizer::{
     tokenize,
+    tokenize_lossy,
     Token,
+    TokenKindCategory,
     TokenType,
     TokenizeError,
 };
@@ -31,3 +47,119 @@ pub fn get_parse_tokens(source: &str) -> Result<Vec<Token>, TokenizeError> {
         .filter(|t| t.typ != TokenType::NL && t.typ != TokenType::COMMENT)
         .collect())
 }
+
+/// Like `get_parse_tokens`, but also return the filtered out comment and
+/// non-logical newline tokens.  Their spans are left intact so that tools such
+/// as formatters can associate comments with the parsed tree by offset.
+pub fn get_parse_tokens_with_trivia(
+    source: &str,
+) -> Result<(Vec<Token>, Vec<Token>), TokenizeError> {
+    let tokens = tokenize(source)?;
+
+    Ok(tokens
+        .into_iter()
+        .partition(|t| t.typ != TokenType::NL && t.typ != TokenType::COMMENT))
+}
+
+/// Tokenize and parse a whole module.
+///
+/// Unlike `parsers::file_input`, this checks that all tokens were consumed
+/// and that imports and exports precede definitions.  Tokenizer and parser
+/// errors are both reported as a `Diagnostic`.
+pub fn parse_module<'a>(source: &'a str) -> Result<Spanned<Module<'a>>, Diagnostic> {
+    let tokens = get_parse_tokens(source)?;
+    let module = match parsers::file_input(&tokens) {
+        Ok((rest, _)) if !rest.is_empty() => {
+            let error = ParseError::static_str(rest, "expected end of input");
+            return Err(Diagnostic::from(&error));
+        }
+        Ok((_, module)) => module,
+        Err(error) => {
+            // `file_input` reports the error of the last module statement
+            // parser it tried, which is rarely the statement that was meant.
+            // The resilient parser reports the one that got furthest.
+            let (_, errors) = parsers::resilient_file_input(&tokens);
+            return Err(Diagnostic::from(errors.first().unwrap_or(&error)));
+        }
+    };
+
+    parsers::validate_module_order(&tokens, &module.node)
+        .map_err(|error| Diagnostic::from(&error))?;
+
+    // SAFETY: see `parse_contract`.  The tree only borrows `source`.
+    let module = unsafe { std::mem::transmute::<Spanned<Module<'_>>, Spanned<Module<'a>>>(module) };
+
+    Ok(module)
+}
+
+/// Tokenize and parse a source file that contains exactly one contract
+/// definition.
+pub fn parse_contract<'a>(source: &'a str) -> Result<Spanned<ModuleStmt<'a>>, Diagnostic> {
+    let tokens = get_parse_tokens(source)?;
+    let (_, contract) =
+        parsers::contract_file_input(&tokens).map_err(|error| Diagnostic::from(&error))?;
+
+    // SAFETY: the parsers tie their output to the lifetime of the token slice,
+    // but every string in a parse tree is a slice of `source` taken from a
+    // token's `string` field, never of the token slice itself.  The tree stays
+    // valid after `tokens` is dropped.
+    let contract = unsafe {
+        std::mem::transmute::<Spanned<ModuleStmt<'_>>, Spanned<ModuleStmt<'a>>>(contract)
+    };
+
+    Ok(contract)
+}
+
+/// The result of tokenizing and parsing a source file.
+///
+/// Building a `Parse` never fails; problems are recorded in `diagnostics`
+/// instead.  `module` is the (possibly partial) module tree, from which
+/// statements that failed to parse are left out.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parse<'a> {
+    pub tokens: Vec<Token<'a>>,
+    pub module: Spanned<Module<'a>>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Parse<'a> {
+    pub fn from_source(source: &'a str) -> Self {
+        let (tokens, errors) = tokenize_lossy(source);
+        let tokens: Vec<_> = tokens
+            .into_iter()
+            .filter(|t| t.typ != TokenType::NL && t.typ != TokenType::COMMENT)
+            .collect();
+
+        let mut diagnostics: Vec<Diagnostic> = errors.into_iter().map(Diagnostic::from).collect();
+        let (module, errors) = parsers::resilient_file_input(&tokens);
+        diagnostics.extend(errors.iter().map(Diagnostic::from));
+
+        // SAFETY: see `parse_contract`.  The tree only borrows `source`.
+        let module =
+            unsafe { std::mem::transmute::<Spanned<Module<'_>>, Spanned<Module<'a>>>(module) };
+
+        Self {
+            tokens,
+            module,
+            diagnostics,
+        }
+    }
+}
+
+/// Serialize a module to JSON for use by external tools.  Spans are written as
+/// `{"start": ..., "end": ...}` objects.
+pub fn to_json(module: &Spanned<Module>) -> String {
+    serde_json::to_string(module).expect("failed to serialize module")
+}
+
+/// Tokenize the given source code in `source` and count the tokens in each
+/// category.  Unlike `get_parse_tokens`, trivia such as comments is included.
+pub fn token_histogram(source: &str) -> Result<HashMap<TokenKindCategory, usize>, TokenizeError> {
+    let mut histogram = HashMap::new();
+
+    for tok in tokenize(source)? {
+        *histogram.entry(tok.category()).or_insert(0) += 1;
+    }
+
+    Ok(histogram)
+}
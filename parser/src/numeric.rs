@@ -0,0 +1,179 @@
+use crate::span::Span;
+use crate::tokenizer::types::{
+    Token,
+    TokenType,
+};
+
+/// The character that may be used to separate digits in numeric literals.
+pub const SEPARATOR: char = '_';
+
+/// Remove digit separators from a numeric literal.  The result has the same
+/// value as `literal`.
+pub fn strip_separators(literal: &str) -> String {
+    literal.chars().filter(|c| *c != SEPARATOR).collect()
+}
+
+/// Return the digits of a hexadecimal literal without its "0x" prefix or
+/// `None` if `literal` is not a hexadecimal literal.
+fn hex_digits(literal: &str) -> Option<&str> {
+    if literal.starts_with("0x") || literal.starts_with("0X") {
+        Some(&literal[2..])
+    } else {
+        None
+    }
+}
+
+/// Return true if `literal` is a hexadecimal literal whose digits are
+/// separated into groups of irregular size.  Separators in hexadecimal
+/// literals are expected to group digits into bytes e.g. "0xde_ad_be_ef".
+/// Literals without separators are always regular.
+pub fn has_irregular_hex_grouping(literal: &str) -> bool {
+    match hex_digits(literal) {
+        Some(digits) if digits.contains(SEPARATOR) => {
+            digits.split(SEPARATOR).any(|group| group.len() != 2)
+        }
+        _ => false,
+    }
+}
+
+/// The kind of number denoted by a numeric literal.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NumClass {
+    Integer,
+    /// A literal with a fractional part or an exponent.
+    Float,
+    /// A literal that does not denote a number.
+    Malformed,
+}
+
+/// Classify a numeric literal as an integer, a float, or malformed.
+///
+/// Decimal literals are floats if they contain a decimal point or an
+/// exponent.  Hexadecimal, binary and octal literals are always integers.
+pub fn classify(literal: &str) -> NumClass {
+    let literal = strip_separators(literal).to_ascii_lowercase();
+    let (digits, radix) = match literal.get(..2) {
+        Some("0x") => (&literal[2..], 16),
+        Some("0b") => (&literal[2..], 2),
+        Some("0o") => (&literal[2..], 8),
+        _ => (&literal[..], 10),
+    };
+
+    if !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix)) {
+        return NumClass::Integer;
+    }
+    if radix != 10 {
+        return NumClass::Malformed;
+    }
+
+    let (mantissa, exponent) = match digits.find('e') {
+        Some(idx) => (&digits[..idx], Some(&digits[idx + 1..])),
+        None => (digits, None),
+    };
+    let valid_mantissa = mantissa.matches('.').count() <= 1
+        && mantissa.chars().any(|c| c.is_ascii_digit())
+        && mantissa.chars().all(|c| c.is_ascii_digit() || c == '.');
+    let valid_exponent = match exponent {
+        Some(exp) => {
+            let exp = exp.strip_prefix(|c| c == '+' || c == '-').unwrap_or(exp);
+            !exp.is_empty() && exp.chars().all(|c| c.is_ascii_digit())
+        }
+        None => true,
+    };
+
+    if valid_mantissa && valid_exponent {
+        NumClass::Float
+    } else {
+        NumClass::Malformed
+    }
+}
+
+/// A warning produced when the digits of a hexadecimal literal are not grouped
+/// into bytes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HexGroupingWarning<'a> {
+    pub literal: &'a str,
+    pub span: Span,
+}
+
+impl<'a> HexGroupingWarning<'a> {
+    pub fn message(&self) -> String {
+        format!(
+            "digits of hexadecimal literal \"{}\" should be grouped into bytes by separators",
+            self.literal,
+        )
+    }
+}
+
+/// Return a warning for every number token in `tokens` that contains a
+/// hexadecimal literal with irregularly grouped digits.
+pub fn hex_grouping_warnings<'a>(tokens: &[Token<'a>]) -> Vec<HexGroupingWarning<'a>> {
+    tokens
+        .iter()
+        .filter(|tok| tok.typ == TokenType::NUMBER && has_irregular_hex_grouping(tok.string))
+        .map(|tok| HexGroupingWarning {
+            literal: tok.string,
+            span: tok.span,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_parse_tokens;
+
+    #[test]
+    fn test_strip_separators() {
+        assert_eq!(strip_separators("1_000_000"), "1000000");
+        assert_eq!(strip_separators("0xde_ad_be_ef"), "0xdeadbeef");
+        assert_eq!(
+            strip_separators("0xd_ead_beef"),
+            strip_separators("0xde_ad_be_ef"),
+        );
+    }
+
+    #[test]
+    fn test_has_irregular_hex_grouping() {
+        assert!(!has_irregular_hex_grouping("0xdeadbeef"));
+        assert!(!has_irregular_hex_grouping("0xde_ad_be_ef"));
+        assert!(!has_irregular_hex_grouping("1_0"));
+        assert!(has_irregular_hex_grouping("0xd_ead"));
+        assert!(has_irregular_hex_grouping("0Xdead_beef"));
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("1"), NumClass::Integer);
+        assert_eq!(classify("1_000"), NumClass::Integer);
+        assert_eq!(classify("0xff"), NumClass::Integer);
+        assert_eq!(classify("0b101"), NumClass::Integer);
+        assert_eq!(classify("1.5"), NumClass::Float);
+        assert_eq!(classify("1."), NumClass::Float);
+        assert_eq!(classify(".5"), NumClass::Float);
+        assert_eq!(classify("1e3"), NumClass::Float);
+        assert_eq!(classify("1.5E-3"), NumClass::Float);
+        assert_eq!(classify("1.2.3"), NumClass::Malformed);
+        assert_eq!(classify("1e"), NumClass::Malformed);
+        assert_eq!(classify("0xfg"), NumClass::Malformed);
+        assert_eq!(classify("1j"), NumClass::Malformed);
+    }
+
+    #[test]
+    fn test_hex_grouping_warnings() {
+        let toks = get_parse_tokens("x = 0xde_ad_be_ef + 0xd_ead").unwrap();
+        let warnings = hex_grouping_warnings(&toks);
+
+        assert_eq!(
+            warnings,
+            vec![HexGroupingWarning {
+                literal: "0xd_ead",
+                span: Span::new(20, 27),
+            }],
+        );
+        assert_eq!(
+            warnings[0].message(),
+            "digits of hexadecimal literal \"0xd_ead\" should be grouped into bytes by separators",
+        );
+    }
+}
@@ -16,11 +16,20 @@ use crate::builders::{
     terminated,
     verify,
 };
-use crate::errors::ParseError;
+use crate::errors::{
+    ParseError,
+    Suggestion,
+};
+use crate::keywords::is_hard_keyword;
+use crate::numeric::{
+    classify,
+    NumClass,
+};
 use crate::span::{
     Span,
     Spanned,
 };
+use crate::string_utils::strip_quotes;
 use crate::tokenizer::types::{
     Token,
     TokenType,
@@ -38,6 +47,16 @@ pub fn next(input: Cursor) -> ParseResult<&Token> {
     }
 }
 
+/// Return the span of the token at the current parse position.  At the end of
+/// the token stream, this is the zero-length span of the `ENDMARKER` token.
+/// An exhausted input yields a zero-length span at offset zero.
+pub fn current_span(input: Cursor) -> Span {
+    match input.first() {
+        Some(tok) => tok.span,
+        None => Span::new(0, 0),
+    }
+}
+
 /// Parse a token of a specific type.
 pub fn token<'a>(typ: TokenType) -> impl Fn(Cursor<'a>) -> ParseResult<&Token> {
     verify(
@@ -47,16 +66,26 @@ pub fn token<'a>(typ: TokenType) -> impl Fn(Cursor<'a>) -> ParseResult<&Token> {
     )
 }
 
-/// Parse a name token.
+/// Parse a name token that may be used as an identifier i.e. one that does
+/// not contain a hard keyword.
 pub fn name_token(input: Cursor) -> ParseResult<&Token> {
-    token(TokenType::NAME)(input)
+    verify(
+        token(TokenType::NAME),
+        |t| !is_hard_keyword(t.string),
+        |inp, t| {
+            ParseError::str(
+                inp,
+                &format!("keyword \"{}\" cannot be used as an identifier", t.string),
+            )
+        },
+    )(input)
 }
 
 /// Parse a name token containing a specific string.
 #[allow(clippy::needless_lifetimes)]
 pub fn name<'a>(string: &'a str) -> impl Fn(Cursor<'a>) -> ParseResult<&Token> {
     verify(
-        name_token,
+        token(TokenType::NAME),
         move |t| t.string == string,
         move |inp, _| ParseError::str(inp, &format!("expected \"{}\" name token", string)),
     )
@@ -77,6 +106,15 @@ pub fn op<'a>(string: &'a str) -> impl Fn(Cursor<'a>) -> ParseResult<&Token> {
     )
 }
 
+/// Parse the colon that begins a block.  If the colon is missing, the error
+/// suggests inserting one before the next token.
+pub fn block_colon(input: Cursor) -> ParseResult<&Token> {
+    op(":")(input).map_err(|err| match input.first() {
+        Some(tok) => err.with_suggestion(Suggestion::insert(tok.span.start, ":")),
+        None => err,
+    })
+}
+
 /// Parse a number token.
 pub fn number_token(input: Cursor) -> ParseResult<&Token> {
     token(TokenType::NUMBER)(input)
@@ -128,18 +166,17 @@ pub fn empty_file_input(input: Cursor) -> ParseResult<Spanned<Module>> {
 
 /// Parse a non-empty module definition.
 pub fn non_empty_file_input(input: Cursor) -> ParseResult<Spanned<Module>> {
-    // module_stmt+
-    let (input, body) = many1(module_stmt)(input)?;
+    let start_span = current_span(input);
+
+    // [docstring] module_stmt+
+    let (input, doc) = opt(module_docstring)(input)?;
+    let (input, stmts) = many1(module_stmt)(input)?;
+    let body: Vec<_> = doc.into_iter().chain(stmts).collect();
 
     // ENDMARKER
     let (input, _) = endmarker_token(input)?;
 
-    let span = {
-        let first = body.first().unwrap();
-        let last = body.last().unwrap();
-
-        Span::from_pair(first, last)
-    };
+    let span = Span::from_slice(&body).unwrap_or(start_span);
 
     Ok((
         input,
@@ -150,14 +187,273 @@ pub fn non_empty_file_input(input: Cursor) -> ParseResult<Spanned<Module>> {
     ))
 }
 
+/// Parse a module definition, recovering from errors in module statements.
+///
+/// A module statement that fails to parse is recorded as an error and skipped
+/// up to the start of the next top-level statement, so a (possibly partial)
+/// module is always produced.
+pub fn resilient_file_input(input: Cursor) -> (Spanned<Module>, Vec<ParseError>) {
+    let start_span = current_span(input);
+    let mut errors = vec![];
+
+    let (mut input, doc) = opt(module_docstring)(input).unwrap_or((input, None));
+    let mut body: Vec<_> = doc.into_iter().collect();
+
+    loop {
+        if let Ok((rest, _)) = newline_token(input) {
+            input = rest;
+            continue;
+        }
+        if input.is_empty() || endmarker_token(input).is_ok() {
+            break;
+        }
+
+        match module_stmt(input) {
+            Ok((rest, stmt)) => {
+                body.push(stmt);
+                input = rest;
+            }
+            Err(_) => {
+                errors.push(furthest_module_stmt_error(input));
+                input = skip_module_stmt(input);
+            }
+        }
+    }
+
+    let span = Span::enclosing(body.iter().map(|stmt| &stmt.span)).unwrap_or(start_span);
+
+    (
+        Spanned {
+            node: Module { body },
+            span,
+        },
+        errors,
+    )
+}
+
+/// The keywords that may begin a module statement.
+pub const MODULE_STMT_KEYWORDS: [&str; 10] = [
+    "import",
+    "from",
+    "export",
+    "type",
+    "const",
+    "static_assert",
+    "pub",
+    "contract",
+    "interface",
+    "struct",
+];
+
+/// Return the error of the module statement parser that got furthest into
+/// `input`.  `module_stmt` only reports the error of its last alternative,
+/// which is rarely the statement the user meant to write.  If no parser got
+/// past the first token, that token is reported as unexpected.  The parsers
+/// must be kept in sync with `module_stmt`.
+#[allow(clippy::type_complexity)]
+fn furthest_module_stmt_error(input: Cursor) -> ParseError {
+    let parsers: [fn(Cursor) -> ParseResult<Spanned<ModuleStmt>>; 8] = [
+        import_stmt,
+        export_stmt,
+        type_def,
+        const_def,
+        static_assert_stmt,
+        contract_def,
+        interface_def,
+        struct_def,
+    ];
+
+    let error = parsers
+        .iter()
+        .filter_map(|parser| parser(input).err())
+        .min_by_key(|error| error.remaining_len())
+        .unwrap();
+
+    if error.remaining_len() == input.len() {
+        ParseError::unexpected_token(input, &MODULE_STMT_KEYWORDS)
+    } else {
+        error
+    }
+}
+
+/// Skip past the statement at the start of `input`, including any indented
+/// block it owns.  At least one token is skipped unless `input` is at the end
+/// of the token stream.
+fn skip_module_stmt(input: Cursor) -> Cursor {
+    let mut depth = 0;
+    let mut rest = input;
+
+    while let Some((tok, remaining)) = rest.split_first() {
+        if tok.typ == TokenType::ENDMARKER {
+            break;
+        }
+        rest = remaining;
+
+        match tok.typ {
+            TokenType::INDENT => depth += 1,
+            TokenType::DEDENT => depth -= 1,
+            _ => {}
+        }
+
+        let at_line_start = matches!(tok.typ, TokenType::NEWLINE | TokenType::DEDENT);
+        let block_follows = matches!(
+            rest.first().map(|tok| tok.typ),
+            Some(TokenType::INDENT) | Some(TokenType::DEDENT)
+        );
+        if depth <= 0 && at_line_start && !block_follows {
+            break;
+        }
+    }
+
+    rest
+}
+
+/// Parse a source file that contains exactly one contract definition.
+pub fn contract_file_input(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    let (input, contract) = contract_def(input)?;
+
+    match endmarker_token(input) {
+        Ok((input, _)) => Ok((input, contract)),
+        Err(_) => Err(ParseError::static_str(input, "expected a single contract")),
+    }
+}
+
+/// Parse a documentation string e.g. "\"Tokens and things\"".
+pub fn docstring(input: Cursor) -> ParseResult<Spanned<Vec<&str>>> {
+    let (input, toks) = terminated(many1(string_token), newline_token)(input)?;
+
+    Ok((
+        input,
+        Spanned {
+            node: toks.iter().map(|tok| strip_quotes(tok.string)).collect(),
+            span: Span::enclosing(toks.iter().map(|tok| &tok.span)).unwrap(),
+        },
+    ))
+}
+
+/// Parse a module docstring.
+pub fn module_docstring(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    map(docstring, |doc| Spanned {
+        node: ModuleStmt::Docstring { lines: doc.node },
+        span: doc.span,
+    })(input)
+}
+
 /// Parse a module statement, such as a contract definition.
 pub fn module_stmt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
-    alt((import_stmt, type_def, contract_def, struct_def))(input)
+    alt((
+        import_stmt,
+        export_stmt,
+        type_def,
+        const_def,
+        static_assert_stmt,
+        contract_def,
+        interface_def,
+        struct_def,
+    ))(input)
+}
+
+/// Parse a compile-time assertion e.g. `static_assert(SIZE <= 32, "too big")`
+/// into its condition, optional message, and span.
+#[allow(clippy::type_complexity)]
+pub fn static_assert(
+    input: Cursor,
+) -> ParseResult<(Spanned<Expr>, Option<Spanned<Vec<&str>>>, Span)> {
+    let (input, kw) = name("static_assert")(input)?;
+    let (input, _) = op("(")(input)?;
+    let (input, cond) = expr(input)?;
+    let (input, msg) = opt(preceded(op(","), many1(string_token)))(input)?;
+    let (input, r_paren) = op(")")(input)?;
+    let (input, _) = newline_token(input)?;
+
+    let msg = msg.map(|toks| Spanned {
+        node: toks.iter().map(|tok| strip_quotes(tok.string)).collect(),
+        span: Span::enclosing(toks.iter().map(|tok| &tok.span)).unwrap(),
+    });
+
+    Ok((input, (cond, msg, Span::from_pair(kw, r_paren))))
+}
+
+/// Parse a module-level compile-time assertion.
+pub fn static_assert_stmt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    map(static_assert, |(cond, msg, span)| Spanned {
+        node: ModuleStmt::StaticAssert { cond, msg },
+        span,
+    })(input)
 }
 
 /// Parse an import statement.
 pub fn import_stmt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
-    terminated(alt((simple_import, from_import)), newline_token)(input)
+    let (rest, stmt) = terminated(alt((simple_import, from_import)), newline_token)(input)?;
+    validate_import(input, &stmt.node)?;
+
+    Ok((rest, stmt))
+}
+
+/// The greatest number of parent levels that a relative import may ascend
+/// before it is considered a mistake.
+pub const MAX_IMPORT_PARENT_LEVEL: usize = 8;
+
+/// Check that the paths in an import statement are well-formed.  Absolute
+/// paths must not be empty and relative paths must not ascend more than
+/// `MAX_IMPORT_PARENT_LEVEL` parent levels.  Errors are reported at `input`,
+/// which should point to the beginning of the statement.
+pub fn validate_import<'a>(input: Cursor<'a>, stmt: &ModuleStmt) -> Result<(), ParseError<'a>> {
+    match stmt {
+        SimpleImport { names } if names.iter().any(|name| name.node.path.is_empty()) => Err(
+            ParseError::static_str(input, "import path must not be empty"),
+        ),
+        FromImport { path, .. } => match &path.node {
+            FromImportPath::Absolute { path } if path.is_empty() => Err(ParseError::static_str(
+                input,
+                "absolute import path must not be empty",
+            )),
+            // The first dot refers to the current package rather than a parent
+            FromImportPath::Relative { dots, .. } if *dots > MAX_IMPORT_PARENT_LEVEL + 1 => {
+                Err(ParseError::str(
+                    input,
+                    &format!(
+                        "relative import ascends more than {} parent levels",
+                        MAX_IMPORT_PARENT_LEVEL,
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Check that import and export statements precede all definitions in a
+/// module.  The module parsers accept statements in any order, so this check
+/// is run over an already parsed module.  Errors are reported at the first
+/// token of the offending statement in `input`, which should hold the tokens
+/// of the whole module.
+pub fn validate_module_order<'a>(input: Cursor<'a>, module: &Module) -> Result<(), ParseError<'a>> {
+    let mut seen_definition = false;
+
+    for stmt in module.body.iter() {
+        let msg = match &stmt.node {
+            SimpleImport { .. } | FromImport { .. } => "import statements must precede definitions",
+            Export { .. } => "export statements must precede definitions",
+            Docstring { .. } => continue,
+            _ => {
+                seen_definition = true;
+                continue;
+            }
+        };
+
+        if seen_definition {
+            let pos = input
+                .iter()
+                .position(|tok| tok.span.start >= stmt.span.start && !tok.is_layout())
+                .unwrap_or(input.len());
+
+            return Err(ParseError::static_str(&input[pos..], msg));
+        }
+    }
+
+    Ok(())
 }
 
 /// Parse an import statement beginning with the "import" keyword.
@@ -187,9 +483,7 @@ pub fn simple_import_name(input: Cursor) -> ParseResult<Spanned<SimpleImportName
     let (input, path) = dotted_name(input)?;
     let (input, alias) = opt(preceded(name("as"), name_token))(input)?;
 
-    let first = path.first().unwrap();
-    let last = path.last().unwrap();
-    let path_span = Span::from_pair(first, last);
+    let path_span = Span::enclosing(path.iter().map(|part| &part.span)).unwrap();
 
     let span = {
         match alias {
@@ -219,16 +513,16 @@ pub fn from_import(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
 /// components.
 pub fn from_import_parent_alt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
     let (input, from_kw) = name("from")(input)?;
-    let (input, parent_level) = dots_to_int(input)?;
+    let (input, dots) = dots_to_int(input)?;
     let (input, _) = name("import")(input)?;
     let (input, names) = from_import_names(input)?;
 
     let path = Spanned {
         node: FromImportPath::Relative {
-            parent_level: parent_level.node,
+            dots: dots.node,
             path: vec![],
         },
-        span: parent_level.span,
+        span: dots.span,
     };
     let span = Span::from_pair(from_kw, names.span);
 
@@ -261,19 +555,17 @@ pub fn from_import_sub_alt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
 
 /// Parse a path containing sub module components in a "from" import statement.
 pub fn from_import_sub_path(input: Cursor) -> ParseResult<Spanned<FromImportPath>> {
-    let (input, opt_parent_level) = opt(dots_to_int)(input)?;
+    let (input, opt_dots) = opt(dots_to_int)(input)?;
     let (input, path) = dotted_name(input)?;
 
-    let first = path.first().unwrap();
-    let last = path.last().unwrap();
-    let span = Span::from_pair(first, last);
+    let span = Span::enclosing(path.iter().map(|part| &part.span)).unwrap();
 
-    let result = match opt_parent_level {
-        Some(parent_level) => {
-            let span = Span::from_pair(&parent_level, span);
+    let result = match opt_dots {
+        Some(dots) => {
+            let span = Span::from_pair(&dots, span);
             Spanned {
                 node: FromImportPath::Relative {
-                    parent_level: parent_level.node,
+                    dots: dots.node,
                     path,
                 },
                 span,
@@ -311,22 +603,27 @@ pub fn from_import_names_star(input: Cursor) -> ParseResult<Spanned<FromImportNa
 }
 
 /// Parse a parenthesized list of names to be imported by a "from" import
-/// statement.
+/// statement.  The list may be empty e.g. "()".
 pub fn from_import_names_parens(input: Cursor) -> ParseResult<Spanned<FromImportNames>> {
     let (input, l_paren) = op("(")(input)?;
-    let (input, names) = from_import_names_list(input)?;
+    let (input, names) = match input.first() {
+        Some(Token { string: ")", .. }) => (input, FromImportNames::List(vec![])),
+        Some(_) => map(from_import_names_list, |names| names.node)(input)?,
+        None => return Err(ParseError::eof(input)),
+    };
     let (input, r_paren) = op(")")(input)?;
 
     Ok((
         input,
         Spanned {
-            node: names.node,
+            node: names,
             span: Span::from_pair(l_paren, r_paren),
         },
     ))
 }
 
-/// Parse a list of names to be imported by a "from" import statement.
+/// Parse a list of names to be imported by a "from" import statement.  Names
+/// are kept in source order.
 pub fn from_import_names_list(input: Cursor) -> ParseResult<Spanned<FromImportNames>> {
     let (input, first_name) = from_import_name(input)?;
     let (input, mut other_names) = many0(preceded(op(","), from_import_name))(input)?;
@@ -336,13 +633,10 @@ pub fn from_import_names_list(input: Cursor) -> ParseResult<Spanned<FromImportNa
     names.append(&mut other_names);
 
     let span = {
-        let first = names.first().unwrap();
+        let names_span = Span::enclosing(names.iter().map(|name| &name.span)).unwrap();
         match comma_tok {
-            Some(tok) => Span::from_pair(first, tok),
-            None => {
-                let last = names.last().unwrap();
-                Span::from_pair(first, last)
-            }
+            Some(tok) => Span::from_pair(names_span, tok),
+            None => names_span,
         }
     };
 
@@ -377,63 +671,260 @@ pub fn from_import_name(input: Cursor) -> ParseResult<Spanned<FromImportName>> {
     ))
 }
 
+/// Parse an export statement.
+///
+/// Example:
+/// export foo.Bar as Baz
+pub fn export_stmt(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    let (input, export_kw) = name("export")(input)?;
+    let (input, name) = alt((
+        map(op("*"), |tok| Spanned {
+            node: ExportName::Star,
+            span: tok.span,
+        }),
+        map(simple_import_name, |spanned| Spanned {
+            node: ExportName::Path(spanned.node),
+            span: spanned.span,
+        }),
+    ))(input)?;
+    let (input, _) = newline_token(input)?;
+
+    let span = Span::from_pair(export_kw, &name);
+
+    Ok((
+        input,
+        Spanned {
+            node: Export { name },
+            span,
+        },
+    ))
+}
+
 /// Parse a dotted import name.
 pub fn dotted_name(input: Cursor) -> ParseResult<Vec<Spanned<&str>>> {
     separated(map(name_token, |t| t.into()), op("."), false)(input)
 }
 
-/// Parse preceding dots used to indicate parent module imports in import
-/// statements.
+/// Parse preceding dots used to indicate relative imports in import
+/// statements and count them.  An ellipsis token counts as three dots.
 pub fn dots_to_int(input: Cursor) -> ParseResult<Spanned<usize>> {
     let (input, toks) = many1(alt((op("."), op("..."))))(input)?;
 
     let value = toks
         .iter()
         .map(|t| if t.string == "." { 1 } else { 3 })
-        .sum::<usize>()
-        - 1;
+        .sum::<usize>();
 
-    let span = {
-        let first = toks.first().unwrap();
-        let last = toks.last().unwrap();
-
-        Span::from_pair(*first, *last)
-    };
+    let span = Span::enclosing(toks.iter().map(|tok| &tok.span)).unwrap();
 
     Ok((input, Spanned { node: value, span }))
 }
 
 /// Parse a contract definition statement.
 pub fn contract_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
-    // "contract" name ":" NEWLINE
+    // ["pub"] "contract" name ["(" name ("," name)* ")"] ":" NEWLINE
+    let (input, pub_kw) = opt(name("pub"))(input)?;
     let (input, contract_kw) = name("contract")(input)?;
+    let name_input = input;
     let (input, name_tok) = name_token(input)?;
-    let (input, _) = op(":")(input)?;
+    let (input, base) = opt(delimited(
+        op("("),
+        separated(name_token, op(","), false),
+        op(")"),
+    ))(input)?;
+    let (input, _) = block_colon(input)?;
     let (input, _) = newline_token(input)?;
 
     // INDENT contract_stmt+ DEDENT
-    let (input, _) = indent_token(input)?;
-    let (input, body) = many1(contract_stmt)(input)?;
+    let body_input = match indent_token(input) {
+        Ok((body_input, _)) => match body_input.first() {
+            Some(tok) if tok.typ != TokenType::DEDENT => body_input,
+            Some(_) => return Err(empty_contract_error(name_input, name_tok)),
+            None => return Err(ParseError::eof(body_input)),
+        },
+        Err(_) => return Err(empty_contract_error(name_input, name_tok)),
+    };
+    let (input, doc) = opt(contract_docstring)(body_input)?;
+    let (input, stmts) = many1(contract_stmt)(input)?;
+    let body: Vec<_> = doc.into_iter().chain(stmts).collect();
     let (input, _) = dedent_token(input)?;
 
-    let last_stmt = body.last().unwrap();
-    let span = Span::from_pair(contract_kw, last_stmt);
+    // Only a single constructor may be defined per contract
+    let mut constructors = body.iter().filter(|stmt| match &stmt.node {
+        ContractStmt::FuncDef { is_constructor, .. } => *is_constructor,
+        _ => false,
+    });
+    if let (Some(_), Some(duplicate)) = (constructors.next(), constructors.next()) {
+        let offset = body_input
+            .iter()
+            .position(|tok| tok.typ != TokenType::DEDENT && tok.span.start == duplicate.span.start)
+            .unwrap();
+
+        return Err(ParseError::static_str(
+            &body_input[offset..],
+            "duplicate definition of constructor \"__init__\"",
+        ));
+    }
+
+    let body_span = Span::from_slice(&body).unwrap_or(name_tok.span);
+    let span = Span::from_pair(pub_kw.unwrap_or(contract_kw), body_span);
 
     Ok((
         input,
         Spanned {
             node: ContractDef {
                 name: name_tok.into(),
+                base: base
+                    .map(|base| base.node.into_iter().map(|tok| tok.into()).collect())
+                    .unwrap_or_default(),
                 body,
+                pub_: pub_kw.is_some(),
             },
             span,
         },
     ))
 }
 
+/// The error for a contract definition without any statements in its body.
+fn empty_contract_error<'a>(name_input: Cursor<'a>, name_tok: &Token) -> ParseError<'a> {
+    ParseError::str(
+        name_input,
+        &format!(
+            "contract \"{}\" has no body; add at least one member",
+            name_tok.string,
+        ),
+    )
+}
+
+/// Parse an interface definition.
+pub fn interface_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    // "interface" name ":" NEWLINE
+    let (input, interface_kw) = name("interface")(input)?;
+    let (input, name_tok) = name_token(input)?;
+    let (input, _) = block_colon(input)?;
+    let (input, _) = newline_token(input)?;
+
+    // INDENT func_sig+ DEDENT
+    let (input, _) = indent_token(input)?;
+    let (input, functions) = many1(func_sig)(input)?;
+    let (input, _) = dedent_token(input)?;
+
+    let last = functions.last().unwrap();
+    let span = Span::from_pair(interface_kw, last);
+
+    Ok((
+        input,
+        Spanned {
+            node: InterfaceDef {
+                name: name_tok.into(),
+                functions,
+            },
+            span,
+        },
+    ))
+}
+
+/// Parse a function signature in an interface definition.  Unlike functions
+/// in contracts, signatures may not have a body.
+pub fn func_sig(input: Cursor) -> ParseResult<Spanned<FuncSig>> {
+    let (input, def_kw) = name("def")(input)?;
+    let (input, name_tok) = name_token(input)?;
+
+    let (
+        input,
+        FuncParams {
+            args,
+            named_returns,
+            return_type,
+            return_type_input,
+            r_paren,
+        },
+    ) = func_params(input, &[])?;
+
+    if !named_returns.is_empty() {
+        return Err(ParseError::str(
+            return_type_input,
+            &format!(
+                "interface function \"{}\" cannot have named return values",
+                name_tok.string,
+            ),
+        ));
+    }
+
+    if op(":")(input).is_ok() {
+        return Err(ParseError::str(
+            input,
+            &format!(
+                "interface function \"{}\" cannot have a body",
+                name_tok.string,
+            ),
+        ));
+    }
+    let (input, _) = newline_token(input)?;
+
+    let span = match &return_type {
+        Some(typ) => Span::from_pair(def_kw, typ),
+        None => Span::from_pair(def_kw, r_paren),
+    };
+
+    Ok((
+        input,
+        Spanned {
+            node: FuncSig {
+                name: name_tok.into(),
+                args,
+                return_type,
+            },
+            span,
+        },
+    ))
+}
+
+/// Parse a contract docstring.
+pub fn contract_docstring(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
+    map(docstring, |doc| Spanned {
+        node: ContractStmt::Docstring { lines: doc.node },
+        span: doc.span,
+    })(input)
+}
+
 /// Parse a contract statement.
 pub fn contract_stmt(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
-    alt((contract_field, event_def, func_def))(input)
+    alt((
+        contract_static_assert,
+        packed_group,
+        contract_field,
+        event_def,
+        func_def,
+        contract_struct_def,
+    ))(input)
+}
+
+/// Parse a struct definition inside a contract.
+pub fn contract_struct_def(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
+    map(struct_def, |stmt| match stmt.node {
+        StructDef {
+            name,
+            body,
+            is_marker,
+        } => Spanned {
+            node: ContractStmt::StructDef {
+                name,
+                body,
+                is_marker,
+            },
+            span: stmt.span,
+        },
+        _ => unreachable!(),
+    })(input)
+}
+
+/// Parse a contract-level compile-time assertion.
+pub fn contract_static_assert(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
+    map(static_assert, |(cond, msg, span)| Spanned {
+        node: ContractStmt::StaticAssert { cond, msg },
+        span,
+    })(input)
 }
 
 /// Parse a contract field definition.
@@ -448,8 +939,10 @@ pub fn contract_field(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
         map(name_token, |tok| (None, tok)),
     ))(input)?;
 
-    let (input, _) = op(":")(input)?;
-    let (input, typ) = type_desc(input)?;
+    let (typ_input, _) = op(":")(input)?;
+    let (input, typ) = type_desc(typ_input)?;
+    // Only function signatures have const generic parameters
+    validate_generic_dims(typ_input, &typ, &[])?;
     let (input, _) = newline_token(input)?;
 
     let span = match &qual {
@@ -470,12 +963,63 @@ pub fn contract_field(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
     ))
 }
 
+/// Parse a group of contract fields to be packed into shared storage slots.
+pub fn packed_group(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
+    // "packed" ":" NEWLINE
+    let (input, packed_kw) = name("packed")(input)?;
+    let (input, _) = op(":")(input)?;
+    let (input, _) = newline_token(input)?;
+
+    // INDENT contract_field+ DEDENT
+    let (input, _) = indent_token(input)?;
+    let (input, fields) = many1(contract_field)(input)?;
+    let (input, _) = dedent_token(input)?;
+
+    let last_field = fields.last().unwrap();
+    let span = Span::from_pair(packed_kw, last_field);
+
+    Ok((
+        input,
+        Spanned {
+            node: ContractStmt::PackedGroup { fields },
+            span,
+        },
+    ))
+}
+
 /// Parse a struct definition statement.
 pub fn struct_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
     // "struct" name ":" NEWLINE
     let (input, contract_kw) = name("struct")(input)?;
     let (input, name_tok) = name_token(input)?;
-    let (input, _) = op(":")(input)?;
+
+    // A marker struct has no colon and no fields
+    if let Ok((input, _)) = newline_token(input) {
+        if indent_token(input).is_ok() {
+            return Err(ParseError::str(
+                input,
+                &format!(
+                    "marker struct \"{}\" cannot have a body; add \":\" after the name to define fields",
+                    name_tok.string,
+                ),
+            )
+            .with_suggestion(Suggestion::insert(name_tok.span.end, ":")));
+        }
+
+        return Ok((
+            input,
+            Spanned {
+                node: StructDef {
+                    name: name_tok.into(),
+                    body: vec![],
+                    is_marker: true,
+                },
+                span: Span::from_pair(contract_kw, name_tok),
+            },
+        ));
+    }
+
+    let (input, _) = block_colon(input)?;
     let (input, _) = newline_token(input)?;
 
     // INDENT struct_field+ DEDENT
@@ -492,6 +1036,7 @@ pub fn struct_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
             node: StructDef {
                 name: name_tok.into(),
                 body,
+                is_marker: false,
             },
             span,
         },
@@ -510,8 +1055,10 @@ pub fn struct_field(input: Cursor) -> ParseResult<Spanned<StructStmt>> {
         map(name_token, |tok| (None, tok)),
     ))(input)?;
 
-    let (input, _) = op(":")(input)?;
-    let (input, typ) = type_desc(input)?;
+    let (typ_input, _) = op(":")(input)?;
+    let (input, typ) = type_desc(typ_input)?;
+    // Only function signatures have const generic parameters
+    validate_generic_dims(typ_input, &typ, &[])?;
     let (input, _) = newline_token(input)?;
 
     let span = match &qual {
@@ -534,19 +1081,39 @@ pub fn struct_field(input: Cursor) -> ParseResult<Spanned<StructStmt>> {
 
 /// Parse an event definition statement.
 pub fn event_def(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
-    // "event" name ":" NEWLINE
+    // "event" name ["anonymous"] ":" NEWLINE
     let (input, event_kw) = name("event")(input)?;
     let (input, name_tok) = name_token(input)?;
-    let (input, _) = op(":")(input)?;
+    let (input, anonymous_kw) = opt(name("anonymous"))(input)?;
+    let (input, _) = block_colon(input)?;
     let (input, _) = newline_token(input)?;
 
-    // INDENT event_field+ DEDENT
+    // INDENT [docstring] event_field+ DEDENT
     let (input, _) = indent_token(input)?;
-    let (input, fields) = many1(event_field)(input)?;
+    let (fields_input, doc) = opt(docstring)(input)?;
+    let (input, fields) = many1(event_field)(fields_input)?;
     let (input, _) = dedent_token(input)?;
 
-    let last_field = fields.last().unwrap();
-    let span = Span::from_pair(event_kw, last_field);
+    // Field names must be unique within an event
+    let duplicate = fields.iter().enumerate().find(|(index, field)| {
+        fields[..*index]
+            .iter()
+            .any(|prev| prev.node.name.node == field.node.name.node)
+    });
+    if let Some((_, duplicate)) = duplicate {
+        let offset = fields_input
+            .iter()
+            .position(|tok| tok.span.start == duplicate.span.start)
+            .unwrap();
+
+        return Err(ParseError::str(
+            &fields_input[offset..],
+            &format!("duplicate event field \"{}\"", duplicate.node.name.node),
+        ));
+    }
+
+    let fields_span = Span::from_slice(&fields).unwrap_or(name_tok.span);
+    let span = Span::from_pair(event_kw, fields_span);
 
     Ok((
         input,
@@ -554,6 +1121,8 @@ pub fn event_def(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
             node: ContractStmt::EventDef {
                 name: name_tok.into(),
                 fields,
+                anonymous: anonymous_kw.is_some(),
+                doc: doc.map(|doc| doc.node),
             },
             span,
         },
@@ -572,8 +1141,10 @@ pub fn event_field(input: Cursor) -> ParseResult<Spanned<EventField>> {
         map(name_token, |tok| (None, tok)),
     ))(input)?;
 
-    let (input, _) = op(":")(input)?;
-    let (input, typ) = type_desc(input)?;
+    let (typ_input, _) = op(":")(input)?;
+    let (input, typ) = type_desc(typ_input)?;
+    // Only function signatures have const generic parameters
+    validate_generic_dims(typ_input, &typ, &[])?;
     let (input, _) = newline_token(input)?;
 
     let span = match &qual {
@@ -594,42 +1165,231 @@ pub fn event_field(input: Cursor) -> ParseResult<Spanned<EventField>> {
     ))
 }
 
+/// The name of the function that is run when a contract is deployed.
+pub const CONSTRUCTOR_NAME: &str = "__init__";
+
+/// Parse a function definition.  A function named "__init__" is treated as the
+/// contract constructor and may not declare a return type.  A function marked
+/// with the "init" modifier is only part of the deployment code.
 pub fn func_def(input: Cursor) -> ParseResult<Spanned<ContractStmt>> {
+    let (input, decorators) = many0(decorator)(input)?;
     let (input, qual) = opt(func_qual)(input)?;
+    let (input, init_kw) = opt(name("init"))(input)?;
     let (input, def_kw) = name("def")(input)?;
     let (input, name_tok) = name_token(input)?;
 
-    let (input, _) = op("(")(input)?;
-    let (input, args) = arg_list(input)?;
-    let (input, _) = op(")")(input)?;
+    let (input, generic_params) = match op("<")(input) {
+        Ok((input, _)) => terminated(generic_params, op(">"))(input)?,
+        Err(_) => (input, vec![]),
+    };
 
-    let (input, return_type) = opt(preceded(op("->"), base_or_tuple_type))(input)?;
+    let (
+        input,
+        FuncParams {
+            args,
+            named_returns,
+            return_type,
+            return_type_input,
+            r_paren: _,
+        },
+    ) = func_params(input, &generic_params)?;
+
+    let is_constructor = name_tok.string == CONSTRUCTOR_NAME;
+    if is_constructor && return_type.is_some() {
+        return Err(ParseError::static_str(
+            return_type_input,
+            "constructor \"__init__\" cannot declare a return type",
+        ));
+    }
 
-    let (input, _) = op(":")(input)?;
+    let (input, _) = block_colon(input)?;
 
     let (input, body) = block(input)?;
 
     let last = body.last().unwrap();
-    let span = match &qual {
-        Some(qual) => Span::from_pair(qual, last),
-        None => Span::from_pair(def_kw, last),
+    let span = match (decorators.first(), &qual, init_kw) {
+        (Some(decorator), _, _) => Span::from_pair(decorator, last),
+        (None, Some(qual), _) => Span::from_pair(qual, last),
+        (None, None, Some(init_kw)) => Span::from_pair(init_kw, last),
+        (None, None, None) => Span::from_pair(def_kw, last),
     };
 
     Ok((
         input,
         Spanned {
             node: ContractStmt::FuncDef {
+                decorators,
                 qual,
                 name: name_tok.into(),
+                generic_params,
                 args,
                 return_type,
+                named_returns,
                 body,
+                is_constructor,
+                init_only: init_kw.is_some(),
             },
             span,
         },
     ))
 }
 
+/// The arguments and return type of a function, shared by function
+/// definitions and interface function signatures.
+pub struct FuncParams<'a> {
+    pub args: Vec<Spanned<FuncDefArg<'a>>>,
+    pub named_returns: Vec<Spanned<FuncDefArg<'a>>>,
+    pub return_type: Option<Spanned<TypeDesc<'a>>>,
+    /// The input following the argument list, where any return type begins.
+    pub return_type_input: Cursor<'a>,
+    pub r_paren: &'a Token<'a>,
+}
+
+/// Parse a parenthesized argument list followed by an optional return type
+/// e.g. "(x: u256) -> bool".  Array dimensions given by name must refer to one
+/// of the const generic parameters in `generic_params`.
+pub fn func_params<'a>(
+    input: Cursor<'a>,
+    generic_params: &[Spanned<GenericParameter>],
+) -> ParseResult<'a, FuncParams<'a>> {
+    let (input, _) = op("(")(input)?;
+    let args_input = input;
+    let (input, args) = arg_list(input)?;
+    let (input, r_paren) = op(")")(input)?;
+
+    let return_type_input = input;
+    let (input, (named_returns, return_type)) = match op("->")(input) {
+        Ok((input, _)) => map(return_spec, |(names, typ)| (names, Some(typ)))(input)?,
+        Err(_) => (input, (vec![], None)),
+    };
+
+    let types = args
+        .iter()
+        .map(|arg| (args_input, &arg.node.typ))
+        .chain(return_type.iter().map(|typ| (return_type_input, typ)));
+    for (typ_input, typ) in types {
+        validate_generic_dims(typ_input, typ, generic_params)?;
+    }
+
+    Ok((
+        input,
+        FuncParams {
+            args,
+            named_returns,
+            return_type,
+            return_type_input,
+            r_paren,
+        },
+    ))
+}
+
+/// Parse a function decorator on its own line e.g. "@payable".
+pub fn decorator(input: Cursor) -> ParseResult<Spanned<&str>> {
+    let (input, at) = op("@")(input)?;
+    let (input, name_tok) = name_token(input)?;
+    let (input, _) = newline_token(input)?;
+
+    Ok((
+        input,
+        Spanned {
+            node: name_tok.string,
+            span: Span::from_pair(at, name_tok),
+        },
+    ))
+}
+
+/// Parse a list of generic parameters e.g. "T, const N: usize".
+pub fn generic_params(input: Cursor) -> ParseResult<Vec<Spanned<GenericParameter>>> {
+    separated(generic_param, op(","), true)(input)
+}
+
+/// Parse a generic parameter.  Const generic parameters must be annotated with
+/// a type.
+///
+/// Example:
+/// const N: usize
+pub fn generic_param(input: Cursor) -> ParseResult<Spanned<GenericParameter>> {
+    if let Ok((input, const_kw)) = name("const")(input) {
+        let (typ_input, name_tok) = name_token(input)?;
+        let (input, typ) = match preceded(op(":"), base_type)(typ_input) {
+            Ok(res) => res,
+            Err(_) => {
+                return Err(ParseError::str(
+                    typ_input,
+                    &format!(
+                        "const generic parameter \"{}\" requires a type annotation",
+                        name_tok.string,
+                    ),
+                ))
+            }
+        };
+
+        let span = Span::from_pair(const_kw, &typ);
+
+        return Ok((
+            input,
+            Spanned {
+                node: GenericParameter::Const {
+                    name: name_tok.into(),
+                    typ,
+                },
+                span,
+            },
+        ));
+    }
+
+    map(name_token, |tok| {
+        Spanned::from_token(tok, GenericParameter::Type { name: tok.into() })
+    })(input)
+}
+
+/// Check that every array dimension given by name in `typ` refers to one of
+/// the const generic parameters in `params`.  Errors are reported at the first
+/// token of `typ` found in `input`.
+pub fn validate_generic_dims<'a>(
+    input: Cursor<'a>,
+    typ: &Spanned<TypeDesc>,
+    params: &[Spanned<GenericParameter>],
+) -> Result<(), ParseError<'a>> {
+    match &typ.node {
+        TypeDesc::GenericArray {
+            typ: inner,
+            dimension,
+        } => {
+            let is_const_param = params.iter().any(|param| match &param.node {
+                GenericParameter::Const { name, .. } => name.node == *dimension,
+                GenericParameter::Type { .. } => false,
+            });
+
+            if !is_const_param {
+                let start = input
+                    .iter()
+                    .position(|tok| tok.span.start == typ.span.start)
+                    .unwrap_or(0);
+
+                return Err(ParseError::str(
+                    &input[start..],
+                    &format!(
+                        "array dimension \"{}\" is not a const generic parameter",
+                        dimension,
+                    ),
+                ));
+            }
+
+            validate_generic_dims(input, inner, params)
+        }
+        TypeDesc::Array { typ: inner, .. } => validate_generic_dims(input, inner, params),
+        TypeDesc::Map { from, to } => {
+            validate_generic_dims(input, from, params)?;
+            validate_generic_dims(input, to, params)
+        }
+        TypeDesc::Tuple { items } => items
+            .iter()
+            .try_for_each(|item| validate_generic_dims(input, item, params)),
+        TypeDesc::Base { .. } | TypeDesc::Int { .. } | TypeDesc::Never => Ok(()),
+    }
+}
+
 pub fn arg_list(input: Cursor) -> ParseResult<Vec<Spanned<FuncDefArg>>> {
     match input[0] {
         Token { string: ")", .. } => Ok((input, vec![])),
@@ -660,18 +1420,46 @@ pub fn arg_def(input: Cursor) -> ParseResult<Spanned<FuncDefArg>> {
 pub fn type_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
     let (input, type_kw) = name("type")(input)?;
     let (input, name) = name_token(input)?;
+    let (typ_input, _) = op("=")(input)?;
+    let (input, type_desc) = type_desc(typ_input)?;
+    validate_generic_dims(typ_input, &type_desc, &[])?;
+    let (input, _) = newline_token(input)?;
+
+    let span = Span::from_pair(type_kw, &type_desc);
+
+    Ok((
+        input,
+        Spanned {
+            node: ModuleStmt::TypeDef {
+                name: name.into(),
+                typ: type_desc,
+            },
+            span,
+        },
+    ))
+}
+
+/// Parse a module-level constant definition e.g. "const MAX: u256 = 2 ** 8".
+pub fn const_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
+    let (input, const_kw) = name("const")(input)?;
+    let (input, name) = name_token(input)?;
+    let (typ_input, _) = op(":")(input)?;
+    let (input, typ) = type_desc(typ_input)?;
+    // Only function signatures have const generic parameters
+    validate_generic_dims(typ_input, &typ, &[])?;
     let (input, _) = op("=")(input)?;
-    let (input, type_desc) = type_desc(input)?;
+    let (input, value) = expr(input)?;
     let (input, _) = newline_token(input)?;
 
-    let span = Span::from_pair(type_kw, &type_desc);
+    let span = Span::from_pair(const_kw, &value);
 
     Ok((
         input,
         Spanned {
-            node: ModuleStmt::TypeDef {
+            node: ModuleStmt::ConstDef {
                 name: name.into(),
-                typ: type_desc,
+                typ,
+                value,
             },
             span,
         },
@@ -680,12 +1468,125 @@ pub fn type_def(input: Cursor) -> ParseResult<Spanned<ModuleStmt>> {
 
 /// Parse a type description e.g. "u256" or "map<address, bool>".
 pub fn type_desc(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
-    alt((map_type, base_type, tuple_type))(input)
+    reject_never_type(input)?;
+    alt((map_type, tuple_type, base_type))(input)
 }
 
 /// Parse all base and tuple types but not map types
 pub fn base_or_tuple_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
-    alt((base_type, tuple_type))(input)
+    alt((tuple_type, base_type))(input)
+}
+
+/// Parse a function return type.  Unlike other types, return types may be
+/// the `never` type.
+pub fn return_type_desc(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
+    alt((never_type, base_or_tuple_type))(input)
+}
+
+/// Parse a function's return type, which may be a tuple of named return
+/// values e.g. "(total: u256, ok: bool)".  The names of any named return
+/// values are returned along with the return type.  A single named return
+/// value has the type of that value rather than a tuple type.  Unnamed return
+/// types are parsed like any other type, so "(u256)" is just "u256".
+#[allow(clippy::type_complexity)]
+pub fn return_spec(input: Cursor) -> ParseResult<(Vec<Spanned<FuncDefArg>>, Spanned<TypeDesc>)> {
+    let (mut input, opening) = match op("(")(input) {
+        Ok((rest, opening)) if has_named_return(rest) => (rest, opening),
+        _ => return map(return_type_desc, |typ| (vec![], typ))(input),
+    };
+
+    let mut named = vec![];
+    let mut has_unnamed = false;
+
+    while op(")")(input).is_err() {
+        let item_input = input;
+
+        match pair(name_token, op(":"))(input) {
+            Ok((rest, (name_tok, _))) => {
+                let (rest, typ) = base_type(rest)?;
+                let span = Span::from_pair(name_tok, &typ);
+
+                named.push(Spanned {
+                    node: FuncDefArg {
+                        name: name_tok.into(),
+                        typ,
+                    },
+                    span,
+                });
+                input = rest;
+            }
+            Err(_) => {
+                let (rest, _) = base_type(input)?;
+
+                has_unnamed = true;
+                input = rest;
+            }
+        }
+
+        if !named.is_empty() && has_unnamed {
+            return Err(ParseError::static_str(
+                item_input,
+                "cannot mix named and unnamed return values",
+            ));
+        }
+
+        match op(",")(input) {
+            Ok((rest, _)) => input = rest,
+            Err(_) => break,
+        }
+    }
+
+    let (input, closing) = op(")")(input)?;
+
+    let typ = match named.as_slice() {
+        [single] => single.node.typ.clone(),
+        _ => Spanned {
+            node: TypeDesc::Tuple {
+                items: named.iter().map(|arg| arg.node.typ.clone()).collect(),
+            },
+            span: Span::from_pair(opening, closing),
+        },
+    };
+
+    Ok((input, (named, typ)))
+}
+
+/// Return true if the parenthesized return type starting at `input` contains a
+/// ":", which only appears in named return values.
+fn has_named_return(input: Cursor) -> bool {
+    let mut depth = 0;
+
+    for tok in input {
+        match tok.string {
+            "(" => depth += 1,
+            ")" if depth == 0 => return false,
+            ")" => depth -= 1,
+            ":" => return true,
+            _ if tok.typ == TokenType::NEWLINE => return false,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Parse the `never` type e.g. "never" or "!".
+pub fn never_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
+    let (input, never_tok) = alt((name("never"), op("!")))(input)?;
+
+    Ok((input, Spanned::from_token(never_tok, TypeDesc::Never)))
+}
+
+/// Return an error if `input` starts with the `never` type, which may only be
+/// used as a return type.
+fn reject_never_type(input: Cursor) -> Result<(), ParseError> {
+    match never_type(input) {
+        Ok(_) => Err(ParseError::static_str(
+            input,
+            "the \"never\" type may only be used as a return type",
+        )),
+        Err(_) => Ok(()),
+    }
 }
 
 /// Parse a map type e.g. "map<address, bool".
@@ -760,20 +1661,24 @@ pub fn map_type_single(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
 /// Example:
 /// int128[2][3]
 pub fn base_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
-    let (input, base) = name_token(input)?;
+    reject_never_type(input)?;
+    let (input, mut result) = match pair(alt((name("uint"), name("int"))), op("<"))(input) {
+        Ok(_) => int_type(input)?,
+        Err(_) => {
+            let (input, base) = name_token(input)?;
+            (input, base.into())
+        }
+    };
     let (input, dims) = arr_list(input)?;
 
-    let mut result = Spanned {
-        node: TypeDesc::Base { base: base.string },
-        span: base.into(),
-    };
     for dim in dims {
         let span = Span::from_pair(&result, &dim);
+        let typ = Box::new(result);
 
         result = Spanned {
-            node: TypeDesc::Array {
-                typ: Box::new(result),
-                dimension: dim.node,
+            node: match dim.node {
+                ArrayDim::Fixed(dimension) => TypeDesc::Array { typ, dimension },
+                ArrayDim::Generic(dimension) => TypeDesc::GenericArray { typ, dimension },
             },
             span,
         };
@@ -782,6 +1687,39 @@ pub fn base_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
     Ok((input, result))
 }
 
+/// Parse an integer type with an explicit bit width between 1 and 256 e.g.
+/// "uint<24>" or "int<8>".
+pub fn int_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
+    let (input, int_kw) = alt((name("uint"), name("int")))(input)?;
+    let (width_input, _) = op("<")(input)?;
+    let (input, width_tok) = number_token(width_input)?;
+    let (input, r_angle) = op(">")(input)?;
+
+    let bits = match width_tok.string.parse::<usize>() {
+        Ok(bits) if (1..=256).contains(&bits) => bits,
+        _ => {
+            return Err(ParseError::str(
+                width_input,
+                &format!(
+                    "integer width \"{}\" must be between 1 and 256 bits",
+                    width_tok.string,
+                ),
+            ))
+        }
+    };
+
+    Ok((
+        input,
+        Spanned {
+            node: TypeDesc::Int {
+                signed: int_kw.string == "int",
+                bits,
+            },
+            span: Span::from_pair(int_kw, r_angle),
+        },
+    ))
+}
+
 /// Parse a tuple type.
 ///
 /// Example:
@@ -789,15 +1727,23 @@ pub fn base_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
 pub fn tuple_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
     let (input, opening) = op("(")(input)?;
 
-    let (input, types) = match input[0] {
-        Token { string: ")", .. } => Ok((input, vec![])),
-        _ => separated(base_type, op(","), true)(input),
+    let (input, (mut types, trailing_comma)) = match input[0] {
+        Token { string: ")", .. } => Ok((input, (vec![], None))),
+        _ => pair(separated(base_type, op(","), false), opt(op(",")))(input),
     }?;
 
     let (input, closing) = op(")")(input)?;
 
+    // A single parenthesized type without a trailing comma e.g. "(u256)" is
+    // just that type, like a parenthesized expression.
+    let node = if types.len() == 1 && trailing_comma.is_none() {
+        types.remove(0).node
+    } else {
+        TypeDesc::Tuple { items: types }
+    };
+
     let result = Spanned {
-        node: TypeDesc::Tuple { items: types },
+        node,
         span: Span::from_pair(opening, closing),
     };
 
@@ -805,8 +1751,32 @@ pub fn tuple_type(input: Cursor) -> ParseResult<Spanned<TypeDesc>> {
 }
 
 /// Parse an array dimension list e.g. "[2][3]"
-pub fn arr_list(input: Cursor) -> ParseResult<Vec<Spanned<usize>>> {
-    many0(arr_dim)(input)
+pub fn arr_list(input: Cursor) -> ParseResult<Vec<Spanned<ArrayDim>>> {
+    many0(alt((
+        map(arr_dim, |dim| Spanned {
+            node: ArrayDim::Fixed(dim.node),
+            span: dim.span,
+        }),
+        map(generic_arr_dim, |dim| Spanned {
+            node: ArrayDim::Generic(dim.node),
+            span: dim.span,
+        }),
+    )))(input)
+}
+
+/// Parse an array dimension given by a const generic parameter e.g. "[N]"
+pub fn generic_arr_dim(input: Cursor) -> ParseResult<Spanned<&str>> {
+    let (input, l_bracket) = op("[")(input)?;
+    let (input, name_tok) = name_token(input)?;
+    let (input, r_bracket) = op("]")(input)?;
+
+    Ok((
+        input,
+        Spanned {
+            node: name_tok.string,
+            span: Span::from_pair(l_bracket, r_bracket),
+        },
+    ))
 }
 
 /// Parse an array dimension e.g. "[2]"
@@ -977,8 +1947,10 @@ pub fn revert_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
 
 pub fn vardecl_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
     let (input, target_expr) = target(input)?;
-    let (input, _) = op(":")(input)?;
-    let (input, typ) = type_desc(input)?;
+    let (typ_input, _) = op(":")(input)?;
+    let (input, typ) = type_desc(typ_input)?;
+    // Only function signatures have const generic parameters
+    validate_generic_dims(typ_input, &typ, &[])?;
     let (input, value) = opt(preceded(op("="), expr))(input)?;
 
     let span = match &value {
@@ -1052,7 +2024,7 @@ pub fn augassign_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
 }
 
 pub fn compound_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
-    alt((if_stmt, while_stmt, for_stmt))(input)
+    alt((if_stmt, while_stmt, for_stmt, unchecked_stmt, yul_stmt))(input)
 }
 
 #[allow(clippy::needless_lifetimes)]
@@ -1064,7 +2036,7 @@ pub fn if_stmt_builder<'a>(
             |input| {
                 let (input, keyword) = name(string)(input)?;
                 let (input, test) = expr(input)?;
-                let (input, _) = op(":")(input)?;
+                let (input, _) = block_colon(input)?;
                 let (input, body) = block(input)?;
                 let (input, or_else) = elif_stmt(input)?;
 
@@ -1086,7 +2058,7 @@ pub fn if_stmt_builder<'a>(
             |input| {
                 let (input, keyword) = name(string)(input)?;
                 let (input, test) = expr(input)?;
-                let (input, _) = op(":")(input)?;
+                let (input, _) = block_colon(input)?;
                 let (input, body) = block(input)?;
                 let (input, or_else) = opt(else_block)(input)?;
 
@@ -1123,7 +2095,7 @@ pub fn elif_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
 
 pub fn else_block(input: Cursor) -> ParseResult<Vec<Spanned<FuncStmt>>> {
     let (input, _) = name("else")(input)?;
-    let (input, _) = op(":")(input)?;
+    let (input, _) = block_colon(input)?;
     let (input, stmts) = block(input)?;
 
     Ok((input, stmts))
@@ -1132,7 +2104,7 @@ pub fn else_block(input: Cursor) -> ParseResult<Vec<Spanned<FuncStmt>>> {
 pub fn while_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
     let (input, while_kw) = name("while")(input)?;
     let (input, test) = expr(input)?;
-    let (input, _) = op(":")(input)?;
+    let (input, _) = block_colon(input)?;
     let (input, body) = block(input)?;
     let (input, or_else) = opt(else_block)(input)?;
 
@@ -1161,7 +2133,7 @@ pub fn for_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
     let (input, target_expr) = targets(input)?;
     let (input, _) = name("in")(input)?;
     let (input, iter) = exprs(input)?;
-    let (input, _) = op(":")(input)?;
+    let (input, _) = block_colon(input)?;
     let (input, body) = block(input)?;
     let (input, or_else) = opt(else_block)(input)?;
 
@@ -1186,6 +2158,71 @@ pub fn for_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
     ))
 }
 
+/// Parse a block of statements in which arithmetic is not checked for
+/// overflow.
+pub fn unchecked_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
+    let (input, unchecked_kw) = name("unchecked")(input)?;
+    let (input, _) = block_colon(input)?;
+    let (input, body) = block(input)?;
+
+    let last_stmt = body.last().unwrap();
+    let span = Span::from_pair(unchecked_kw, last_stmt);
+
+    Ok((
+        input,
+        Spanned {
+            node: FuncStmt::Unchecked { body },
+            span,
+        },
+    ))
+}
+
+/// Parse a block of inline Yul code.  The indented body is captured as raw
+/// source text and is not validated.
+pub fn yul_stmt(input: Cursor) -> ParseResult<Spanned<FuncStmt>> {
+    let (input, yul_kw) = name("yul")(input)?;
+    let (input, _) = op(":")(input)?;
+    let (input, _) = newline_token(input)?;
+    let (mut input, _) = indent_token(input)?;
+
+    let mut code = vec![];
+    let mut depth = 0;
+
+    loop {
+        let (rest, tok) = next(input)?;
+
+        match tok.typ {
+            TokenType::INDENT => depth += 1,
+            TokenType::DEDENT if depth == 0 => {
+                input = rest;
+                break;
+            }
+            TokenType::DEDENT => depth -= 1,
+            TokenType::ENDMARKER => return Err(ParseError::eof(input)),
+            _ => {}
+        }
+
+        if !tok.is_layout() && !tok.is_trivia() {
+            code.push(tok.span);
+        }
+        input = rest;
+    }
+
+    let source_span = match Span::enclosing(&code) {
+        Some(span) => span,
+        None => return Err(ParseError::static_str(input, "expected yul code")),
+    };
+    let span = Span::from_pair(yul_kw, source_span);
+
+    Ok((
+        input,
+        Spanned {
+            node: FuncStmt::Yul { source_span },
+            span,
+        },
+    ))
+}
+
 pub fn block(input: Cursor) -> ParseResult<Vec<Spanned<FuncStmt>>> {
     alt((simple_stmt, |input| {
         let (input, _) = newline_token(input)?;
@@ -1376,17 +2413,55 @@ pub fn shift_expr(input: Cursor) -> ParseResult<Spanned<Expr>> {
 }
 
 pub fn sum(input: Cursor) -> ParseResult<Spanned<Expr>> {
-    op_expr_builder(term, alt((op("+"), op("-"))), bin_op_builder)(input)
+    op_expr_builder(
+        term,
+        alt((op("+"), op("-"), op("+%"), op("-%"))),
+        bin_op_builder,
+    )(input)
 }
 
 pub fn term(input: Cursor) -> ParseResult<Spanned<Expr>> {
     op_expr_builder(
-        factor,
-        alt((op("*"), op("/"), op("//"), op("%"))),
+        cast,
+        alt((op("*"), op("/"), op("//"), op("%"), op("*%"))),
         bin_op_builder,
     )(input)
 }
 
+/// Parse a chain of casts e.g. "x as u8", "x as! u8", or "x as? u8".  A "!"
+/// or "?" only modifies the cast mode when it immediately follows "as".
+pub fn cast(input: Cursor) -> ParseResult<Spanned<Expr>> {
+    let (mut input, mut result) = factor(input)?;
+
+    while let Ok((rest, as_kw)) = name("as")(input) {
+        let attached = |tok: &Token| tok.span.start == as_kw.span.end;
+
+        let (rest, mode) = match op_token(rest) {
+            Ok((after_mode, tok)) if tok.string == "!" && attached(tok) => {
+                (after_mode, CastMode::Unchecked)
+            }
+            Ok((after_mode, tok)) if tok.string == "?" && attached(tok) => {
+                (after_mode, CastMode::ExplicitChecked)
+            }
+            _ => (rest, CastMode::Checked),
+        };
+        let (rest, typ) = base_type(rest)?;
+
+        let span = Span::from_pair(&result, &typ);
+        result = Spanned {
+            node: Expr::Cast {
+                value: Box::new(result),
+                typ,
+                mode,
+            },
+            span,
+        };
+        input = rest;
+    }
+
+    Ok((input, result))
+}
+
 pub fn factor(input: Cursor) -> ParseResult<Spanned<Expr>> {
     let unary_op = |input| {
         let (input, op_tok) = alt((op("+"), op("-"), op("~")))(input)?;
@@ -1407,23 +2482,26 @@ pub fn factor(input: Cursor) -> ParseResult<Spanned<Expr>> {
 }
 
 pub fn power(input: Cursor) -> ParseResult<Spanned<Expr>> {
-    let power_op = |input| {
-        let (input, primary_expr) = primary(input)?;
-        let (input, op_tok) = op("**")(input)?;
-        let (input, factor_expr) = factor(input)?;
+    // The primary expression is only parsed once, so that nested expressions
+    // don't take time exponential in their depth to parse.
+    let (input, primary_expr) = primary(input)?;
 
-        let span = Span::from_pair(&primary_expr, &factor_expr);
+    match op("**")(input) {
+        Ok((input, op_tok)) => {
+            let (input, factor_expr) = factor(input)?;
 
-        Ok((
-            input,
-            Spanned {
-                node: bin_op_builder(primary_expr, op_tok, factor_expr),
-                span,
-            },
-        ))
-    };
+            let span = Span::from_pair(&primary_expr, &factor_expr);
 
-    alt((power_op, primary))(input)
+            Ok((
+                input,
+                Spanned {
+                    node: bin_op_builder(primary_expr, op_tok, factor_expr),
+                    span,
+                },
+            ))
+        }
+        Err(_) => Ok((input, primary_expr)),
+    }
 }
 
 pub fn build_tail_expr<'a>(exp: Spanned<Expr<'a>>, tails: Vec<Tail<'a>>) -> Spanned<Expr<'a>> {
@@ -1453,12 +2531,13 @@ pub fn build_tail_expr<'a>(exp: Spanned<Expr<'a>>, tails: Vec<Tail<'a>>) -> Span
                     span,
                 };
             }
-            Tail::Call(args) => {
+            Tail::Call(call_modifiers, args) => {
                 let span = Span::from_pair(&result, &args);
 
                 result = Spanned {
                     node: Expr::Call {
                         func: Box::new(result),
+                        call_modifiers,
                         args,
                     },
                     span,
@@ -1520,43 +2599,43 @@ pub fn slice(input: Cursor) -> ParseResult<Spanned<Slice>> {
 
 pub fn atom(input: Cursor) -> ParseResult<Spanned<Expr>> {
     alt((
-        map(name("true"), |tok| Spanned {
-            node: Expr::Bool(true),
-            span: tok.span,
+        map(name("true"), |tok| {
+            Spanned::from_token(tok, Expr::Bool(true))
         }),
-        map(name("false"), |tok| Spanned {
-            node: Expr::Bool(false),
-            span: tok.span,
+        map(name("false"), |tok| {
+            Spanned::from_token(tok, Expr::Bool(false))
         }),
         list,
+        operator_ref,
+        comptime_expr,
         map(group, |exp| Spanned {
             node: exp.node.node,
             span: exp.span,
         }),
         tuple,
-        map(name_token, |tok| Spanned {
-            node: Expr::Name(tok.string),
-            span: tok.span,
+        map(name_token, |tok| {
+            Spanned::from_token(tok, Expr::Name(tok.string))
         }),
-        map(number_token, |tok| Spanned {
-            node: Expr::Num(tok.string),
-            span: tok.span,
+        map(number_token, |tok| {
+            let node = Expr::Num {
+                value: tok.string,
+                is_float: classify(tok.string) == NumClass::Float,
+            };
+
+            Spanned::from_token(tok, node)
         }),
         map(many1(string_token), |toks| {
             let tok_strings: Vec<_> = toks
                 .iter()
                 .map(|t| {
                     // We don't want to carry quotes around strings past the parsing stage
-                    &t.string[1..t.string.len() - 1]
+                    strip_quotes(t.string)
                 })
                 .collect();
 
-            let fst = toks.first().unwrap();
-            let snd = toks.last().unwrap();
-
             Spanned {
                 node: Expr::Str(tok_strings),
-                span: Span::from_pair(*fst, *snd),
+                span: Span::enclosing(toks.iter().map(|tok| &tok.span)).unwrap(),
             }
         }),
         map(op("..."), |tok| Spanned {
@@ -1567,24 +2646,112 @@ pub fn atom(input: Cursor) -> ParseResult<Spanned<Expr>> {
 }
 
 pub fn list(input: Cursor) -> ParseResult<Spanned<Expr>> {
-    map(delimited(op("["), opt(exprs), op("]")), |spanned| {
-        use Expr::{
-            List,
-            Tuple,
-        };
+    const MIXED_SEPARATORS: &str = "cannot mix \";\" and \",\" in an array literal";
 
-        let node = match spanned.node {
-            Some(Spanned {
-                node: Tuple { elts },
-                ..
-            }) => List { elts },
-            Some(exp) => List { elts: vec![exp] },
-            None => List { elts: vec![] },
-        };
-        let span = spanned.span;
+    let (input, l_bracket) = op("[")(input)?;
 
-        Spanned { node, span }
-    })(input)
+    if let Ok((input, r_bracket)) = op("]")(input) {
+        return Ok((
+            input,
+            Spanned {
+                node: Expr::List { elts: vec![] },
+                span: Span::from_pair(l_bracket, r_bracket),
+            },
+        ));
+    }
+
+    // The first element is only parsed once, the separator after it decides
+    // which kind of array literal this is.
+    let (input, first) = expr(input)?;
+
+    // Array literals with a repeated value e.g. "[0; 32]"
+    if let Ok((input, _)) = op(";")(input) {
+        let (input, count) = expr(input)?;
+        if op(",")(input).is_ok() {
+            return Err(ParseError::static_str(input, MIXED_SEPARATORS));
+        }
+        let (input, r_bracket) = op("]")(input)?;
+
+        return Ok((
+            input,
+            Spanned {
+                node: Expr::ArrayRepeat {
+                    value: Box::new(first),
+                    count: Box::new(count),
+                },
+                span: Span::from_pair(l_bracket, r_bracket),
+            },
+        ));
+    }
+
+    let (input, mut rest) = many0(preceded(op(","), expr))(input)?;
+    let (input, _) = opt(op(","))(input)?;
+    if op(";")(input).is_ok() {
+        return Err(ParseError::static_str(input, MIXED_SEPARATORS));
+    }
+    let (input, r_bracket) = op("]")(input)?;
+
+    let mut elts = vec![first];
+    elts.append(&mut rest);
+
+    let list = Spanned {
+        node: Expr::List { elts },
+        span: Span::from_pair(l_bracket, r_bracket),
+    };
+
+    // Array literals with an element type annotation e.g. "[1, 2, 3]: u8[3]".
+    // Only numeric array types are accepted so that a list followed by an
+    // inline block (e.g. "for x in [1, 2]: y[0] = x") is left alone.
+    match preceded(op(":"), base_type)(input) {
+        Ok((annotated_input, typ)) if is_numeric_array_type(&typ.node) => {
+            let span = Span::from_pair(&list, &typ);
+            let elts = match list.node {
+                Expr::List { elts } => elts,
+                _ => unreachable!(),
+            };
+
+            Ok((
+                annotated_input,
+                Spanned {
+                    node: Expr::AnnotatedList { elts, typ },
+                    span,
+                },
+            ))
+        }
+        _ => Ok((input, list)),
+    }
+}
+
+/// Return true if a type description is a (possibly nested) array of a
+/// numeric type e.g. "u8[3]" or "uint<24>[2][2]".
+fn is_numeric_array_type(typ: &TypeDesc) -> bool {
+    fn is_numeric(typ: &TypeDesc) -> bool {
+        match typ {
+            TypeDesc::Int { .. } => true,
+            TypeDesc::Base { base } => matches!(
+                *base,
+                "u256"
+                    | "u128"
+                    | "u64"
+                    | "u32"
+                    | "u16"
+                    | "u8"
+                    | "i256"
+                    | "i128"
+                    | "i64"
+                    | "i32"
+                    | "i16"
+                    | "i8"
+            ),
+            TypeDesc::Array { typ, .. } => is_numeric(&typ.node),
+            _ => false,
+        }
+    }
+
+    match typ {
+        TypeDesc::Array { typ, .. } => is_numeric(&typ.node),
+        _ => false,
+    }
 }
 
 pub fn tuple(input: Cursor) -> ParseResult<Spanned<Expr>> {
@@ -1605,6 +2772,53 @@ pub fn tuple(input: Cursor) -> ParseResult<Spanned<Expr>> {
     })(input)
 }
 
+/// Parse an expression that must be evaluated at compile time e.g.
+/// "comptime(2 ** 8)".
+pub fn comptime_expr(input: Cursor) -> ParseResult<Spanned<Expr>> {
+    let (input, comptime_kw) = name("comptime")(input)?;
+    let (input, _) = op("(")(input)?;
+    let (input, inner) = expr(input)?;
+    let (input, r_paren) = op(")")(input)?;
+
+    Ok((
+        input,
+        Spanned {
+            node: Expr::Comptime {
+                inner: Box::new(inner),
+            },
+            span: Span::from_pair(comptime_kw, r_paren),
+        },
+    ))
+}
+
+/// Parse a parenthesized binary operator that is referred to as a value e.g.
+/// "(+)" or "(and)".
+pub fn operator_ref(input: Cursor) -> ParseResult<Spanned<Expr>> {
+    let (op_input, l_paren) = op("(")(input)?;
+    let (input, op_tok) = next(op_input)?;
+    let (input, r_paren) = op(")")(input)?;
+
+    let operator = if let Ok(comp_op) = Spanned::<CompOperator>::try_from(&[op_tok][..]) {
+        Operator::Comp(comp_op.node)
+    } else if let Ok(bool_op) = Spanned::<BoolOperator>::try_from(op_tok) {
+        Operator::Bool(bool_op.node)
+    } else {
+        // Augmented assignment tokens such as "+=" are not operators
+        match Spanned::<BinOperator>::try_from(op_tok) {
+            Ok(bin_op) if !op_tok.string.ends_with('=') => Operator::Bin(bin_op.node),
+            _ => return Err(ParseError::static_str(op_input, "expected an operator")),
+        }
+    };
+
+    Ok((
+        input,
+        Spanned {
+            node: Expr::OperatorRef(operator),
+            span: Span::from_pair(l_paren, r_paren),
+        },
+    ))
+}
+
 pub fn group(input: Cursor) -> ParseResult<Spanned<Spanned<Expr>>> {
     delimited(op("("), expr, op(")"))(input)
 }
@@ -1655,7 +2869,10 @@ pub fn kwarg(input: Cursor) -> ParseResult<Spanned<CallArg>> {
 pub enum Tail<'a> {
     Attr(&'a Token<'a>),
     Index(Spanned<Vec<Spanned<Slice<'a>>>>),
-    Call(Spanned<Vec<Spanned<CallArg<'a>>>>),
+    Call(
+        Vec<Spanned<CallModifier<'a>>>,
+        Spanned<Vec<Spanned<CallArg<'a>>>>,
+    ),
 }
 
 pub fn targets(input: Cursor) -> ParseResult<Spanned<Expr>> {
@@ -1714,9 +2931,8 @@ pub fn target(input: Cursor) -> ParseResult<Spanned<Expr>> {
 
 pub fn t_atom(input: Cursor) -> ParseResult<Spanned<Expr>> {
     alt((
-        map(name_token, |tok| Spanned {
-            node: Expr::Name(tok.string),
-            span: tok.span,
+        map(name_token, |tok| {
+            Spanned::from_token(tok, Expr::Name(tok.string))
         }),
         map(delimited(op("("), targets, op(")")), |spanned| {
             use Expr::Tuple;
@@ -1770,11 +2986,44 @@ pub fn index_tail(input: Cursor) -> ParseResult<Tail> {
 }
 
 pub fn call_tail(input: Cursor) -> ParseResult<Tail> {
-    map(
-        map(delimited(op("("), opt(args), op(")")), |spanned| Spanned {
+    let (input, modifiers) = opt(delimited(op("{"), call_modifiers, op("}")))(input)?;
+    let (input, args) = map(
+        delimited(op("("), opt(terminated(args, opt(op(",")))), op(")")),
+        |spanned| Spanned {
             node: spanned.node.unwrap_or_else(Vec::new),
             span: spanned.span,
-        }),
-        Tail::Call,
-    )(input)
+        },
+    )(input)?;
+
+    let modifiers = match modifiers {
+        Some(spanned) => spanned.node,
+        None => vec![],
+    };
+
+    Ok((input, Tail::Call(modifiers, args)))
+}
+
+/// Parse a list of call modifiers e.g. "value: 1, gas: 10000".
+pub fn call_modifiers(input: Cursor) -> ParseResult<Vec<Spanned<CallModifier>>> {
+    separated(call_modifier, op(","), true)(input)
+}
+
+/// Parse a call modifier e.g. "value: 1".
+pub fn call_modifier(input: Cursor) -> ParseResult<Spanned<CallModifier>> {
+    let (input, name_tok) = name_token(input)?;
+    let (input, _) = op(":")(input)?;
+    let (input, value_expr) = expr(input)?;
+
+    let span = Span::from_pair(name_tok, &value_expr);
+
+    Ok((
+        input,
+        Spanned {
+            node: CallModifier {
+                name: name_tok.into(),
+                value: Box::new(value_expr),
+            },
+            span,
+        },
+    ))
 }
@@ -3,6 +3,9 @@ use serde::{
     Serialize,
 };
 
+use crate::string_utils::lines_with_endings;
+use crate::tokenizer::types::Token;
+
 /// An exclusive span of byte offsets in a source file.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Hash, Eq)]
 pub struct Span {
@@ -32,6 +35,54 @@ impl Span {
             end: end_span.end,
         }
     }
+
+    /// Return the smallest span that encloses all of the items in `items` or
+    /// `None` if there are no items.
+    pub fn from_slice<'a, T>(items: &'a [T]) -> Option<Span>
+    where
+        &'a T: Into<Span>,
+    {
+        let spans: Vec<Span> = items.iter().map(Into::into).collect();
+        Span::enclosing(&spans)
+    }
+
+    /// Return the smallest span that encloses all of the spans in `items` or
+    /// `None` if there are no items.
+    pub fn enclosing<'a>(items: impl IntoIterator<Item = &'a Span>) -> Option<Span> {
+        items.into_iter().fold(None, |result, span| match result {
+            Some(Span { start, end }) => Some(Span {
+                start: start.min(span.start),
+                end: end.max(span.end),
+            }),
+            None => Some(*span),
+        })
+    }
+
+    /// Return the line and column of the start and end of the span in
+    /// `source`.  Lines are 1-indexed and columns are 0-indexed counts of
+    /// characters, not bytes, from the beginning of the line.
+    pub fn line_col(&self, source: &str) -> ((usize, usize), (usize, usize)) {
+        (line_col(source, self.start), line_col(source, self.end))
+    }
+}
+
+/// Return the line and column of byte offset `offset` in `source`.  Offsets
+/// past the end of `source` are clamped to the end.
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let mut line_count = 0;
+
+    for (line, start, end) in lines_with_endings(source) {
+        line_count += 1;
+
+        if offset < end || !line.ends_with('\n') {
+            return (line_count, source[start..offset].chars().count());
+        }
+    }
+
+    // The offset is at the end of a source that is empty or ends with a
+    // line ending
+    (line_count + 1, 0)
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -40,6 +91,40 @@ pub struct Spanned<T> {
     pub span: Span,
 }
 
+impl<T> Spanned<T> {
+    #[inline]
+    pub fn new(node: T, span: Span) -> Self {
+        Self { node, span }
+    }
+
+    /// Create a spanned node that covers the single token `tok`.
+    #[inline]
+    pub fn from_token(tok: &Token, node: T) -> Self {
+        Self {
+            node,
+            span: tok.span,
+        }
+    }
+
+    /// Transform the node with `f`, keeping the span.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned {
+            node: f(self.node),
+            span: self.span,
+        }
+    }
+
+    /// Borrow the node, keeping the span.
+    #[inline]
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned {
+            node: &self.node,
+            span: self.span,
+        }
+    }
+}
+
 impl<T> From<&Spanned<T>> for Span {
     fn from(spanned: &Spanned<T>) -> Self {
         spanned.span
@@ -51,3 +136,79 @@ impl<T> From<&Box<Spanned<T>>> for Span {
         spanned.span
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::types::TokenType;
+
+    #[test]
+    fn test_enclosing() {
+        let spans = [Span::new(4, 6), Span::new(0, 2), Span::new(8, 10)];
+
+        assert_eq!(Span::enclosing(&spans), Some(Span::new(0, 10)));
+        assert_eq!(Span::enclosing(&spans[..1]), Some(Span::new(4, 6)));
+        assert_eq!(Span::enclosing(&[]), None);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let items = [
+            Spanned::new("foo", Span::new(0, 3)),
+            Spanned::new("bar", Span::new(4, 7)),
+            Spanned::new("baz", Span::new(8, 11)),
+        ];
+
+        assert_eq!(Span::from_slice(&items), Some(Span::new(0, 11)));
+        assert_eq!(Span::from_slice(&items[1..2]), Some(Span::new(4, 7)));
+        assert_eq!(Span::from_slice::<Spanned<&str>>(&[]), None);
+    }
+
+    #[test]
+    fn test_line_col() {
+        let source = "foo\r\nbär = 1\n  baz\n";
+
+        // "=" comes after the two byte "ä"
+        assert_eq!(Span::new(10, 11).line_col(source), ((2, 4), (2, 5)));
+        // "baz"
+        assert_eq!(Span::new(16, 19).line_col(source), ((3, 2), (3, 5)));
+        // "foo" through the line ending
+        assert_eq!(Span::new(0, 5).line_col(source), ((1, 0), (2, 0)));
+        // the end of the source
+        assert_eq!(Span::new(20, 20).line_col(source), ((4, 0), (4, 0)));
+        assert_eq!(Span::new(2, 3).line_col("abc"), ((1, 2), (1, 3)));
+        assert_eq!(Span::new(0, 0).line_col(""), ((1, 0), (1, 0)));
+    }
+
+    #[test]
+    fn test_spanned_new() {
+        let spanned = Spanned::new("foo", Span::new(1, 4));
+
+        assert_eq!(spanned.node, "foo");
+        assert_eq!(spanned.span, Span::new(1, 4));
+    }
+
+    #[test]
+    fn test_spanned_from_token() {
+        let tok = Token {
+            typ: TokenType::NAME,
+            string: "foo",
+            span: Span::new(4, 7),
+            line: "bar foo",
+        };
+        let spanned = Spanned::from_token(&tok, tok.string);
+
+        assert_eq!(spanned, Spanned::new("foo", Span::new(4, 7)));
+    }
+
+    #[test]
+    fn test_spanned_map() {
+        let spanned = Spanned::new("foo", Span::new(1, 4));
+
+        assert_eq!(spanned.as_ref(), Spanned::new(&"foo", Span::new(1, 4)));
+        assert_eq!(
+            spanned.map(|node| node.len()),
+            Spanned::new(3, Span::new(1, 4))
+        );
+    }
+}
@@ -3,6 +3,8 @@ use serde::{
     Serialize,
 };
 
+use crate::span::Span;
+
 /// Iterate over the lines in `buf` and include line endings in the results.
 /// Also, provide byte offsets of line beginnings and endings.
 pub fn lines_with_endings(buf: &str) -> impl Iterator<Item = (&str, usize, usize)> {
@@ -34,6 +36,40 @@ pub fn lines_with_endings(buf: &str) -> impl Iterator<Item = (&str, usize, usize
     })
 }
 
+/// The kind of line endings used in a string.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LineEndingReport {
+    /// The string contains no line endings.
+    None,
+    /// All lines end with "\n".
+    Lf,
+    /// All lines end with "\r\n".
+    Crlf,
+    /// Some lines end with "\n" and others with "\r\n".
+    Mixed,
+}
+
+/// Determine the kind of line endings used in `source`.
+pub fn detect_line_endings(source: &str) -> LineEndingReport {
+    let mut lf = false;
+    let mut crlf = false;
+
+    for (line, _, _) in lines_with_endings(source) {
+        if line.ends_with("\r\n") {
+            crlf = true;
+        } else if line.ends_with('\n') {
+            lf = true;
+        }
+    }
+
+    match (lf, crlf) {
+        (false, false) => LineEndingReport::None,
+        (true, false) => LineEndingReport::Lf,
+        (false, true) => LineEndingReport::Crlf,
+        (true, true) => LineEndingReport::Mixed,
+    }
+}
+
 /// Strip the characters in the string `strip` from the left side of the string
 /// slice `input`.
 pub fn lstrip_slice<'a>(input: &'a str, strip: &str) -> &'a str {
@@ -66,6 +102,55 @@ pub fn rstrip_slice<'a>(input: &'a str, strip: &str) -> &'a str {
     &input[..end]
 }
 
+/// Remove the quotes surrounding the string literal `literal`.  Both single
+/// and triple quotes are removed.
+pub fn strip_quotes(literal: &str) -> &str {
+    for quote in &["\"\"\"", "'''", "\"", "'"] {
+        if literal.len() >= 2 * quote.len()
+            && literal.starts_with(quote)
+            && literal.ends_with(quote)
+        {
+            return &literal[quote.len()..literal.len() - quote.len()];
+        }
+    }
+
+    literal
+}
+
+/// Join the unquoted lines of a string literal and replace escape sequences
+/// with the characters they denote.  Unknown escape sequences are kept as
+/// written.
+pub fn decode_string(lines: &[&str]) -> String {
+    let mut result = String::new();
+
+    for line in lines {
+        let mut chars = line.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('r') => result.push('\r'),
+                Some('0') => result.push('\0'),
+                Some('\n') => {}
+                Some(c @ '\\') | Some(c @ '"') | Some(c @ '\'') => result.push(c),
+                Some(c) => {
+                    result.push('\\');
+                    result.push(c);
+                }
+                None => result.push('\\'),
+            }
+        }
+    }
+
+    result
+}
+
 /// A position in a source file specified by a 1-indexed line number and a
 /// 0-indexed byte offset into the line specified by that number.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
@@ -176,6 +261,64 @@ impl<'a> StringPositions<'a> {
     }
 }
 
+/// An index of the byte offsets at which each line in a string begins.
+pub struct LineIndex<'a> {
+    /// A string whose lines are indexed
+    input: &'a str,
+    /// The byte offset of the beginning of each line in `input`
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Build an index of the lines in `input`.
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            line_starts: lines_with_endings(input)
+                .map(|(_, start, _)| start)
+                .collect(),
+        }
+    }
+
+    /// Get the 0-indexed number of the line containing byte offset `offset`.
+    /// Offsets past the end of the string belong to the last line.
+    fn line_of(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line.saturating_sub(1),
+        }
+    }
+
+    /// Get the byte offset at which the content of the 0-indexed line `line`
+    /// ends, excluding any line ending characters.
+    fn line_end(&self, line: usize) -> usize {
+        let start = self.line_starts.get(line).copied().unwrap_or(0);
+        let end = match self.line_starts.get(line + 1) {
+            Some(next_start) => *next_start,
+            None => self.input.len(),
+        };
+
+        start + rstrip_slice(&self.input[start..end], "\r\n").len()
+    }
+
+    /// Expand `span` to cover the full lines that it touches, from the start of
+    /// its first line to the end of its last line (excluding the line
+    /// ending).  A zero-length span resolves to the line on which it starts.
+    pub fn line_span(&self, span: &Span) -> Span {
+        let first_line = self.line_of(span.start);
+        let last_line = if span.end > span.start {
+            self.line_of(span.end - 1)
+        } else {
+            first_line
+        };
+
+        Span::new(
+            self.line_starts.get(first_line).copied().unwrap_or(0),
+            self.line_end(last_line),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +438,30 @@ here
         }
     }
 
+    #[test]
+    fn test_line_span() {
+        let input = "contract Foo:\n    pub x: u256\r\n    pub y: bool\n";
+        let index = LineIndex::new(input);
+
+        // Span in the middle of a line
+        assert_eq!(index.line_span(&Span::new(22, 23)), Span::new(14, 29));
+
+        // Span touching multiple lines
+        assert_eq!(index.line_span(&Span::new(9, 26)), Span::new(0, 29));
+        assert_eq!(index.line_span(&Span::new(22, 35)), Span::new(14, 46));
+
+        // Span ending just after a line ending stays on that line
+        assert_eq!(index.line_span(&Span::new(2, 14)), Span::new(0, 13));
+
+        // Zero-length span on a line boundary
+        assert_eq!(index.line_span(&Span::new(14, 14)), Span::new(14, 29));
+        assert_eq!(index.line_span(&Span::new(47, 47)), Span::new(31, 46));
+
+        // Empty string
+        let index = LineIndex::new("");
+        assert_eq!(index.line_span(&Span::new(0, 0)), Span::new(0, 0));
+    }
+
     #[test]
     fn test_file_positions() {
         // Empty string has expected behavior
@@ -369,4 +536,33 @@ positions on lines
         assert_eq!(string_pos.get_last(), Some(Position::new(3, 18)));
         assert_eq!(string_pos.get_eof(), Position::new(4, 0));
     }
+
+    #[test]
+    fn test_strip_quotes() {
+        assert_eq!(strip_quotes(r#""abc""#), "abc");
+        assert_eq!(strip_quotes("'abc'"), "abc");
+        assert_eq!(strip_quotes(r#""""abc""""#), "abc");
+        assert_eq!(strip_quotes("'''abc'''"), "abc");
+        assert_eq!(strip_quotes(r#""""#), "");
+        assert_eq!(strip_quotes("abc"), "abc");
+    }
+
+    #[test]
+    fn test_decode_string() {
+        assert_eq!(decode_string(&[]), "");
+        assert_eq!(decode_string(&["abc", "def"]), "abcdef");
+        assert_eq!(
+            decode_string(&[r#"a\tb\n\"c\" \\ \q"#]),
+            "a\tb\n\"c\" \\ \\q",
+        );
+    }
+
+    #[test]
+    fn test_detect_line_endings() {
+        assert_eq!(detect_line_endings(""), LineEndingReport::None);
+        assert_eq!(detect_line_endings("a"), LineEndingReport::None);
+        assert_eq!(detect_line_endings("a\nb\n"), LineEndingReport::Lf);
+        assert_eq!(detect_line_endings("a\r\nb"), LineEndingReport::Crlf);
+        assert_eq!(detect_line_endings("a\r\nb\nc"), LineEndingReport::Mixed);
+    }
 }
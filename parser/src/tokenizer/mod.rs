@@ -25,10 +25,11 @@
 //!   string. This differs from Python's `TokenInfo` instances which use
 //!   line/column tuples to represent the beginning and ending positions of a
 //!   token.
-//! * The [`self::tokenize::tokenize`] function has no streaming behavior and
-//!   accepts a reference to an entire source string available in memory.
-//!   Python's `tokenize.tokenize` function accepts a reference to a function
-//!   that progressively yields lines of text from a source file.
+//! * The [`self::tokenize::tokenize`] function accepts a reference to an entire
+//!   source string available in memory.  Python's `tokenize.tokenize` function
+//!   accepts a reference to a function that progressively yields lines of text
+//!   from a source file.  [`self::tokenize::token_iter`] yields tokens lazily,
+//!   but also requires the whole source string.
 //!
 //! As Python's `tokenize` module's implementation is pretty ugly, so is the
 //! implementation of [`self::tokenize::tokenize`].  It may be a candidate for
@@ -41,10 +42,17 @@ pub mod types;
 pub mod wasm;
 
 pub use self::tokenize::{
+    token_iter,
     tokenize,
+    tokenize_lossy,
+    tokenize_with_options,
     TokenizeError,
+    TokenizeOptions,
 };
 pub use self::types::{
+    find_token_by_text,
+    streams_equivalent,
     Token,
+    TokenKindCategory,
     TokenType,
 };
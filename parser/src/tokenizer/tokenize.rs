@@ -1,10 +1,15 @@
 use regex::Regex;
+use std::collections::{
+    HashSet,
+    VecDeque,
+};
 
 use crate::span::Span;
 use crate::string_utils::{
     lines_with_endings,
     lstrip_slice,
     rstrip_slice,
+    StringPositions,
 };
 use crate::tokenizer::regex::{
     compile_anchored,
@@ -18,11 +23,25 @@ use crate::tokenizer::regex::{
 };
 use crate::tokenizer::types::{
     Token,
+    TokenType,
     TokenType::*,
 };
 
+/// The default number of columns between tab stops.
 const TABSIZE: usize = 8;
 
+/// Return true if `prefix` is the radix prefix of a number literal e.g. "0x".
+fn is_radix_prefix(prefix: &str) -> bool {
+    matches!(prefix, "0x" | "0X" | "0b" | "0B" | "0o" | "0O")
+}
+
+/// Return true if the string literal `token` has an "f" prefix e.g. `f"{x}"`.
+/// Formatted string literals are not supported.
+fn is_formatted_string(token: &str) -> bool {
+    let prefix_len = token.find(&['"', '\''][..]).unwrap_or(0);
+    token[..prefix_len].contains(&['f', 'F'][..])
+}
+
 #[inline]
 fn is_identifier_char(c: char) -> bool {
     c == '_' || c.is_ascii_alphabetic() || c.is_digit(10)
@@ -32,6 +51,47 @@ fn is_identifier_char(c: char) -> bool {
 pub struct TokenizeError {
     pub msg: &'static str,
     pub offset: usize,
+    /// The 1-indexed line number at `offset`
+    pub line: usize,
+    /// The 0-indexed byte offset into the line at `offset`
+    pub col: usize,
+}
+
+impl TokenizeError {
+    /// Create an error located at byte offset `offset` in `input`.
+    pub fn new(input: &str, msg: &'static str, offset: usize) -> Self {
+        let mut positions = StringPositions::new(input);
+        let pos = match positions.get_pos(offset) {
+            Some(pos) => pos,
+            None => positions.get_eof(),
+        };
+
+        Self {
+            msg,
+            offset,
+            line: pos.line,
+            col: pos.col,
+        }
+    }
+}
+
+/// Options that control how the tokenizer reads its input.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TokenizeOptions {
+    /// The greatest length in bytes of a string literal, including its prefix
+    /// and quotes.  String literals are not limited if this is `None`.
+    pub max_string_len: Option<usize>,
+    /// The number of columns between tab stops when measuring indentation.
+    pub tab_size: usize,
+}
+
+impl Default for TokenizeOptions {
+    fn default() -> Self {
+        Self {
+            max_string_len: None,
+            tab_size: TABSIZE,
+        }
+    }
 }
 
 /// Parse a source string into a vector of tokens.
@@ -43,31 +103,184 @@ pub struct TokenizeError {
 /// Returns:
 ///
 /// A vector of tokens.
-#[allow(clippy::cognitive_complexity)]
-#[allow(clippy::trivial_regex)]
 pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
+    tokenize_with_options(input, &TokenizeOptions::default())
+}
+
+/// Parse a source string into a vector of tokens using the tab size in
+/// `options` and rejecting input that exceeds its limits.
+pub fn tokenize_with_options<'a>(
+    input: &'a str,
+    options: &TokenizeOptions,
+) -> Result<Vec<Token<'a>>, TokenizeError> {
+    tokenize_impl(input, options, None)
+}
+
+/// Parse a source string into a vector of tokens without stopping at the
+/// first error.  Problems are returned alongside the tokens and the input
+/// around them is covered by `ERRORTOKEN` tokens where possible, so tokens are
+/// produced for the whole input.
+pub fn tokenize_lossy<'a>(input: &'a str) -> (Vec<Token<'a>>, Vec<TokenizeError>) {
+    let mut errors = vec![];
+    let tokens = tokenize_impl(input, &TokenizeOptions::default(), Some(&mut errors))
+        .expect("lossy tokenization should not fail");
+
+    (tokens, errors)
+}
+
+/// Lazily tokenize a source string.  Tokens are produced a line at a time, so
+/// a consumer that only needs the first few tokens does not pay for the whole
+/// input.  The first error ends the iteration.
+pub fn token_iter<'a>(input: &'a str) -> impl Iterator<Item = Result<Token<'a>, TokenizeError>> {
+    TokenIter::new(input, &TokenizeOptions::default(), false)
+}
+
+/// Tokenize `input`.  If `errors` is given, errors are collected into it and
+/// tokenizing continues.  Otherwise, the first error is returned.
+fn tokenize_impl<'a>(
+    input: &'a str,
+    options: &TokenizeOptions,
+    errors: Option<&mut Vec<TokenizeError>>,
+) -> Result<Vec<Token<'a>>, TokenizeError> {
+    let mut iter = TokenIter::new(input, options, errors.is_some());
+    let result = iter.by_ref().collect();
+
+    if let Some(errors) = errors {
+        errors.append(&mut iter.errors);
+    }
+
+    result
+}
+
+/// Record an error.  Unless tokenizing lossily, the error ends the iteration
+/// and the rest of the current line is abandoned.
+macro_rules! report {
+    ($self:ident, $error:expr) => {{
+        let error = $error;
+        if $self.lossy {
+            $self.errors.push(error)
+        } else {
+            $self.pending.push_back(Err(error));
+            $self.done = true;
+            return;
+        }
+    }};
+}
+
+/// An iterator over the tokens of a source string.  A line of input is only
+/// tokenized once the tokens of the previous line have been consumed.
+struct TokenIter<'a> {
+    input: &'a str,
+    options: TokenizeOptions,
+    /// If true, errors are collected into `errors` rather than ending the
+    /// iteration.
+    lossy: bool,
+    errors: Vec<TokenizeError>,
+
+    lines: Box<dyn Iterator<Item = (&'a str, usize, usize)> + 'a>,
+    /// Tokens and errors that have been produced but not yet yielded
+    pending: VecDeque<Result<Token<'a>, TokenizeError>>,
+    /// The type of the last token produced
+    last_typ: Option<TokenType>,
+    /// True once a line without any content has been read
+    at_eof: bool,
+    /// True once no more tokens will be produced
+    done: bool,
+
     // Static values/helpers
-    let pseudo_token_re = compile_anchored(&get_pseudotoken_pattern());
+    pseudo_token_re: Regex,
+    triple_quoted: HashSet<String>,
+    single_quoted: HashSet<String>,
+    double3_re: Regex,
+    single3_re: Regex,
+    double_re: Regex,
+    single_re: Regex,
+
+    // State vars
+    parenlev: usize,
+    continued: bool,
+    // The column and whitespace prefix of each indentation level
+    indents: Vec<(usize, &'a str)>,
+
+    contstr_start: Option<usize>,
+    contline_start: Option<usize>,
+    contstr_end_re: Option<Regex>,
+    needcont: bool,
+}
+
+impl<'a> Iterator for TokenIter<'a> {
+    type Item = Result<Token<'a>, TokenizeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            if self.done {
+                return None;
+            }
+
+            let line = if self.at_eof { None } else { self.lines.next() };
+            match line {
+                Some((line, line_start, line_end)) => {
+                    self.tokenize_line(line, line_start, line_end)
+                }
+                None => self.finish(),
+            }
+        }
+    }
+}
 
-    let triple_quoted = get_triple_quote_set();
-    let single_quoted = get_single_quote_set();
+impl<'a> TokenIter<'a> {
+    #[allow(clippy::trivial_regex)]
+    fn new(input: &'a str, options: &TokenizeOptions, lossy: bool) -> Self {
+        Self {
+            input,
+            options: *options,
+            lossy,
+            errors: vec![],
+
+            lines: Box::new(lines_with_endings(input)),
+            pending: VecDeque::new(),
+            last_typ: None,
+            at_eof: false,
+            done: false,
+
+            pseudo_token_re: compile_anchored(&get_pseudotoken_pattern()),
+            triple_quoted: get_triple_quote_set(),
+            single_quoted: get_single_quote_set(),
+            double3_re: Regex::new(DOUBLE3).unwrap(),
+            single3_re: Regex::new(SINGLE3).unwrap(),
+            double_re: Regex::new(DOUBLE).unwrap(),
+            single_re: Regex::new(SINGLE).unwrap(),
+
+            parenlev: 0,
+            continued: false,
+            indents: vec![(0, "")],
+
+            contstr_start: None,
+            contline_start: None,
+            contstr_end_re: None,
+            needcont: false,
+        }
+    }
 
-    let double3_re = Regex::new(DOUBLE3).unwrap();
-    let single3_re = Regex::new(SINGLE3).unwrap();
-    let double_re = Regex::new(DOUBLE).unwrap();
-    let single_re = Regex::new(SINGLE).unwrap();
+    fn push(&mut self, token: Token<'a>) {
+        self.last_typ = Some(token.typ);
+        self.pending.push_back(Ok(token));
+    }
 
     // The ordering of checks matters here.  We need to eliminate the possibility of
     // triple quote delimiters before looking for single quote delimiters.
-    let get_contstr_end_re = |token: &str| -> &Regex {
+    fn get_contstr_end_re(&self, token: &str) -> Regex {
         let token_stripped = lstrip_slice(token, "bBrRuUfF");
 
         if token_stripped.starts_with("\"\"\"") {
-            &double3_re
+            self.double3_re.clone()
         } else if token_stripped.starts_with("'''") {
-            &single3_re
+            self.single3_re.clone()
         } else if token_stripped.starts_with('"') {
-            &double_re
+            self.double_re.clone()
         } else {
             // This arm of the if statement is equivalent to the following check:
             // `else if token_stripped.starts_with('\'')`
@@ -75,63 +288,77 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
             // This is because any string in `token` has already been matched against a
             // regex that ensures it begins with """, ''', ", or ' after
             // stripping of any leading prefix codes
-            &single_re
+            self.single_re.clone()
         }
-    };
-
-    // Token list result
-    let mut result: Vec<Token<'a>> = Vec::new();
+    }
 
-    // State vars
-    let mut parenlev: usize = 0;
-    let mut continued: bool = false;
-    let mut indents: Vec<usize> = vec![0];
+    // Strings are checked against the length limit as they are read so that a
+    // long multi-line string is rejected before its end is found.  Errors are
+    // reported at the first byte past the limit.
+    fn check_string_len(&self, start: usize, end: usize) -> Result<(), TokenizeError> {
+        match self.options.max_string_len {
+            Some(max_len) if end - start > max_len => Err(TokenizeError::new(
+                self.input,
+                "string literal exceeds the maximum length",
+                start + max_len,
+            )),
+            _ => Ok(()),
+        }
+    }
 
-    let mut contstr_start: Option<usize> = None;
-    let mut contline_start: Option<usize> = None;
-    let mut contstr_end_re: Option<&Regex> = None;
-    let mut needcont: bool = false;
+    #[allow(clippy::cognitive_complexity)]
+    fn tokenize_line(&mut self, line: &'a str, line_start: usize, line_end: usize) {
+        let input = self.input;
+        let tab_size = self.options.tab_size.max(1);
 
-    for (line, line_start, line_end) in lines_with_endings(input) {
         // Set parsing position relative to this line
         let mut line_pos: usize = 0;
         let line_len: usize = line.len();
 
-        if let Some(contstr_start_val) = contstr_start {
+        if let Some(contstr_start_val) = self.contstr_start {
             // Continued string
-            if let Some(endmatch) = contstr_end_re.unwrap().find(line) {
+            let endmatch = self.contstr_end_re.as_ref().unwrap().find(line);
+            if let Some(endmatch) = endmatch {
                 let tok_end = endmatch.end();
+                if let Err(error) = self.check_string_len(contstr_start_val, line_start + tok_end) {
+                    report!(self, error)
+                }
                 line_pos = tok_end;
 
-                result.push(Token {
+                self.push(Token {
                     typ: STRING,
                     string: &input[contstr_start_val..line_start + tok_end],
                     span: Span::new(contstr_start_val, line_start + tok_end),
-                    line: &input[contline_start.unwrap()..line_end],
+                    line: &input[self.contline_start.unwrap()..line_end],
                 });
 
-                contstr_start = None;
-                contline_start = None;
+                self.contstr_start = None;
+                self.contline_start = None;
 
-                needcont = false;
-            } else if needcont && !line.ends_with("\\\n") && !line.ends_with("\\\r\n") {
-                result.push(Token {
-                    typ: ERRORTOKEN,
-                    string: &input[contstr_start_val..line_end],
-                    span: Span::new(contstr_start_val, line_end),
-                    line: &input[contline_start.unwrap()..line_start],
-                });
+                self.needcont = false;
+            } else {
+                if let Err(error) = self.check_string_len(contstr_start_val, line_end) {
+                    report!(self, error)
+                }
 
-                contstr_start = None;
-                contline_start = None;
+                if self.needcont && !line.ends_with("\\\n") && !line.ends_with("\\\r\n") {
+                    self.push(Token {
+                        typ: ERRORTOKEN,
+                        string: &input[contstr_start_val..line_end],
+                        span: Span::new(contstr_start_val, line_end),
+                        line: &input[self.contline_start.unwrap()..line_start],
+                    });
 
-                continue;
-            } else {
-                continue;
+                    self.contstr_start = None;
+                    self.contline_start = None;
+                }
+
+                return;
             }
-        } else if parenlev == 0 && !continued {
+        } else if self.parenlev == 0 && !self.continued {
             // New statement
             let mut column: usize = 0;
+            let mut indent_start: usize = 0;
 
             // Measure leading whitespace
             for c in line.chars() {
@@ -140,11 +367,12 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                         column += 1;
                     }
                     '\t' => {
-                        column = (column / TABSIZE + 1) * TABSIZE;
+                        column = (column / tab_size + 1) * tab_size;
                     }
                     '\x0c' => {
                         // Form feed ("\f" in python)
                         column = 0;
+                        indent_start = line_pos + 1;
                     }
                     _ => {
                         // Break if we encounter anything that's not part of indentation
@@ -156,8 +384,9 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
 
             if line_pos == line_len {
                 // If no more chars in line (not even newline, carriage return, etc.), we're at
-                // EOF.  Break out of the token loop.
-                break;
+                // EOF.  Stop reading lines.
+                self.at_eof = true;
+                return;
             }
 
             {
@@ -167,7 +396,7 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                         let comment_token = rstrip_slice(&line[line_pos..], "\r\n");
                         let comment_token_len = comment_token.len();
 
-                        result.push(Token {
+                        self.push(Token {
                             typ: COMMENT,
                             string: comment_token,
                             span: Span::new(
@@ -180,22 +409,23 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                         line_pos += comment_token_len;
                     }
 
-                    result.push(Token {
+                    self.push(Token {
                         typ: NL,
                         string: &line[line_pos..],
                         span: Span::new(line_start + line_pos, line_end),
                         line,
                     });
 
-                    continue;
+                    return;
                 }
             }
 
             let rest_off = line_start + line_pos;
+            let prefix = &line[indent_start..line_pos];
 
-            if column > *indents.last().unwrap() {
-                indents.push(column);
-                result.push(Token {
+            if column > self.indents.last().unwrap().0 {
+                self.indents.push((column, prefix));
+                self.push(Token {
                     typ: INDENT,
                     string: &line[..line_pos],
                     span: Span::new(line_start, rest_off),
@@ -203,16 +433,43 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                 });
             }
 
-            if !indents.contains(&column) {
-                return Err(TokenizeError {
-                    msg: "unindent does not match any outer indentation level",
-                    offset: rest_off,
-                });
+            let level_prefix = self
+                .indents
+                .iter()
+                .find(|(level, _)| *level == column)
+                .map(|(_, level_prefix)| *level_prefix);
+            match level_prefix {
+                // Lines at the same level must be indented with the same whitespace
+                Some(level_prefix) if prefix != level_prefix => {
+                    let common_len = prefix
+                        .bytes()
+                        .zip(level_prefix.bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+
+                    report!(
+                        self,
+                        TokenizeError::new(
+                            input,
+                            "inconsistent use of tabs and spaces in indentation",
+                            line_start + indent_start + common_len,
+                        )
+                    )
+                }
+                Some(_) => {}
+                None => report!(
+                    self,
+                    TokenizeError::new(
+                        input,
+                        "unindent does not match any outer indentation level",
+                        rest_off,
+                    )
+                ),
             }
 
-            while column < *indents.last().unwrap() {
-                indents.pop();
-                result.push(Token {
+            while column < self.indents.last().unwrap().0 {
+                self.indents.pop();
+                self.push(Token {
                     typ: DEDENT,
                     string: &line[line_pos..line_pos],
                     span: Span::new(rest_off, rest_off),
@@ -220,11 +477,12 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                 });
             }
         } else {
-            continued = false;
+            self.continued = false;
         }
 
         while line_pos < line_len {
-            if let Some(pseudomatch) = pseudo_token_re.captures(&line[line_pos..]) {
+            let pseudomatch = self.pseudo_token_re.captures(&line[line_pos..]);
+            if let Some(pseudomatch) = pseudomatch {
                 let capture = pseudomatch.get(1).unwrap();
                 let tok_start = line_pos + capture.start();
                 let tok_end = line_pos + capture.end();
@@ -241,57 +499,133 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                 let initial = line[tok_start..].chars().next().unwrap();
 
                 if initial.is_ascii_digit() || (initial == '.' && token != "." && token != "...") {
-                    result.push(Token {
+                    // Radix prefixed literals e.g. "0x1f", "0b1010", or "0o755"
+                    let is_prefixed = token.len() > 2 && is_radix_prefix(&token[..2]);
+                    let rest = &line[tok_end..];
+                    let offending_len = |rest: &str| {
+                        rest.chars()
+                            .next()
+                            .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                            .map_or(0, char::len_utf8)
+                    };
+
+                    let bare_prefix = line.get(tok_start..tok_end + 1);
+                    if token == "0"
+                        && matches!(bare_prefix, Some(prefix) if is_radix_prefix(prefix))
+                    {
+                        // A prefix without any valid digits e.g. "0b2"
+                        let end = tok_end + 1 + offending_len(&rest[1..]);
+                        self.push(Token {
+                            typ: ERRORTOKEN,
+                            string: &line[tok_start..end],
+                            span: Span::new(soff, line_start + end),
+                            line,
+                        });
+                        line_pos = end;
+                        continue;
+                    }
+
+                    self.push(Token {
                         typ: NUMBER,
                         string: token,
                         span: Span::new(soff, eoff),
                         line,
                     });
+
+                    // A digit that is invalid for the literal's base e.g. the
+                    // "2" in "0b102"
+                    let len = offending_len(rest);
+                    if is_prefixed && len > 0 {
+                        self.push(Token {
+                            typ: ERRORTOKEN,
+                            string: &rest[..len],
+                            span: Span::new(eoff, eoff + len),
+                            line,
+                        });
+                        line_pos += len;
+                    }
                 } else if initial == '\r' || initial == '\n' {
-                    result.push(Token {
-                        typ: if parenlev > 0 { NL } else { NEWLINE },
+                    self.push(Token {
+                        typ: if self.parenlev > 0 { NL } else { NEWLINE },
                         string: token,
                         span: Span::new(soff, eoff),
                         line,
                     });
                 } else if initial == '#' {
-                    result.push(Token {
+                    self.push(Token {
                         typ: COMMENT,
                         string: token,
                         span: Span::new(soff, eoff),
                         line,
                     });
-                } else if triple_quoted.contains(token) {
-                    contstr_end_re = Some(get_contstr_end_re(token));
+                } else if self.triple_quoted.contains(token) {
+                    if is_formatted_string(token) {
+                        report!(
+                            self,
+                            TokenizeError::new(
+                                input,
+                                "formatted string literals are not supported",
+                                soff
+                            )
+                        )
+                    }
+
+                    let contstr_end_re = self.get_contstr_end_re(token);
+                    let endmatch = contstr_end_re.find_at(line, line_pos).map(|m| m.end());
+                    self.contstr_end_re = Some(contstr_end_re);
 
-                    if let Some(endmatch) = contstr_end_re.unwrap().find_at(line, line_pos) {
-                        line_pos = endmatch.end();
+                    if let Some(endmatch_end) = endmatch {
+                        line_pos = endmatch_end;
+                        if let Err(error) = self.check_string_len(soff, line_start + line_pos) {
+                            report!(self, error)
+                        }
                         let token = &line[tok_start..line_pos];
 
-                        result.push(Token {
+                        self.push(Token {
                             typ: STRING,
                             string: token,
                             span: Span::new(soff, line_start + line_pos),
                             line,
                         });
                     } else {
-                        contstr_start = Some(line_start + tok_start);
-                        contline_start = Some(line_start);
+                        if let Err(error) = self.check_string_len(soff, line_end) {
+                            report!(self, error)
+                        }
+                        self.contstr_start = Some(line_start + tok_start);
+                        self.contline_start = Some(line_start);
                         break;
                     }
-                } else if single_quoted.contains(&initial.to_string())
-                    || single_quoted.contains(&token.chars().take(2).collect::<String>())
-                    || single_quoted.contains(&token.chars().take(3).collect::<String>())
+                } else if self.single_quoted.contains(&initial.to_string())
+                    || self
+                        .single_quoted
+                        .contains(&token.chars().take(2).collect::<String>())
+                    || self
+                        .single_quoted
+                        .contains(&token.chars().take(3).collect::<String>())
                 {
+                    if is_formatted_string(token) {
+                        report!(
+                            self,
+                            TokenizeError::new(
+                                input,
+                                "formatted string literals are not supported",
+                                soff
+                            )
+                        )
+                    }
+                    if let Err(error) = self.check_string_len(soff, eoff) {
+                        report!(self, error)
+                    }
+
                     if token.ends_with('\n') {
-                        contstr_end_re = Some(get_contstr_end_re(token));
+                        self.contstr_end_re = Some(self.get_contstr_end_re(token));
 
-                        contstr_start = Some(line_start + tok_start);
-                        contline_start = Some(line_start);
+                        self.contstr_start = Some(line_start + tok_start);
+                        self.contline_start = Some(line_start);
 
-                        needcont = true;
+                        self.needcont = true;
                     } else {
-                        result.push(Token {
+                        self.push(Token {
                             typ: STRING,
                             string: token,
                             span: Span::new(soff, eoff),
@@ -299,27 +633,28 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                         });
                     }
                 } else if is_identifier_char(initial) {
-                    result.push(Token {
+                    self.push(Token {
                         typ: NAME,
                         string: token,
                         span: Span::new(soff, eoff),
                         line,
                     });
                 } else if initial == '\\' {
-                    continued = true;
+                    self.continued = true;
                 } else {
                     if initial == '(' || initial == '[' || initial == '{' {
-                        parenlev += 1;
+                        self.parenlev += 1;
                     } else if initial == ')' || initial == ']' || initial == '}' {
-                        if parenlev == 0 {
-                            return Err(TokenizeError {
-                                msg: "Unbalanced brackets",
-                                offset: line_pos,
-                            });
+                        if self.parenlev == 0 {
+                            report!(
+                                self,
+                                TokenizeError::new(input, "Unbalanced brackets", line_pos)
+                            );
+                        } else {
+                            self.parenlev -= 1;
                         }
-                        parenlev -= 1;
                     }
-                    result.push(Token {
+                    self.push(Token {
                         typ: OP,
                         string: token,
                         span: Span::new(soff, eoff),
@@ -328,10 +663,28 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
                 }
             } else {
                 let char = line[line_pos..].chars().next().unwrap();
+
+                // When tokenizing lossily, an unterminated string becomes a
+                // single error token that extends to the end of the line
+                let rest = lstrip_slice(&line[line_pos..], " \t\x0c");
+                if self.lossy && (rest.starts_with('"') || rest.starts_with('\'')) {
+                    let string = rstrip_slice(rest, "\r\n");
+                    let soff = line_end - rest.len();
+                    self.push(Token {
+                        typ: ERRORTOKEN,
+                        string,
+                        span: Span::new(soff, soff + string.len()),
+                        line,
+                    });
+                    report!(self, TokenizeError::new(input, "unterminated string", soff));
+                    line_pos = soff + string.len() - line_start;
+                    continue;
+                }
+
                 let len = char.len_utf8();
                 let string = &line[line_pos..line_pos + len];
                 #[allow(clippy::range_plus_one)]
-                result.push(Token {
+                self.push(Token {
                     typ: ERRORTOKEN,
                     string,
                     span: Span::new(line_start + line_pos, line_start + line_pos + len),
@@ -342,62 +695,78 @@ pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenizeError> {
         }
     }
 
-    // Ensure brackets are balanced
-    if parenlev != 0 {
-        return Err(TokenizeError {
-            msg: "Unbalanced brackets",
-            offset: input.len(),
-        });
-    }
-
-    // We use this zero-length slice as the ending content for remaining tokens.
-    // This is *just in case* anyone actually cares that the location of the
-    // pointer makes any kind of sense.
-    let input_len = input.len();
-    let empty_end_slice = &input[input_len..];
+    /// Check the state left after the last line and produce the tokens that
+    /// end every token stream.
+    fn finish(&mut self) {
+        let input = self.input;
+        self.done = true;
+
+        // Ensure brackets are balanced
+        if self.parenlev != 0 {
+            report!(
+                self,
+                TokenizeError::new(input, "Unbalanced brackets", input.len())
+            )
+        }
 
-    if contstr_start.is_some() {
-        return Err(TokenizeError {
-            msg: "EOF in multi-line string",
-            offset: input_len,
-        });
-    }
+        // We use this zero-length slice as the ending content for remaining tokens.
+        // This is *just in case* anyone actually cares that the location of the
+        // pointer makes any kind of sense.
+        let input_len = input.len();
+        let empty_end_slice = &input[input_len..];
+
+        // Report unterminated strings at the opening quote rather than at the end
+        // of the file
+        if let Some(contstr_start_val) = self.contstr_start {
+            self.push(Token {
+                typ: ERRORTOKEN,
+                string: &input[contstr_start_val..],
+                span: Span::new(contstr_start_val, input_len),
+                line: &input[self.contline_start.unwrap()..],
+            });
+            report!(
+                self,
+                TokenizeError::new(
+                    input,
+                    "unterminated multi-line string starting here",
+                    contstr_start_val,
+                )
+            )
+        }
 
-    if continued {
-        return Err(TokenizeError {
-            msg: "EOF in multi-line statement",
-            offset: input_len,
-        });
-    }
+        if self.continued {
+            report!(
+                self,
+                TokenizeError::new(input, "EOF in multi-line statement", input_len)
+            )
+        }
 
-    // Ensure content tokens end with newline (this allows parsers to be defined
-    // more consistently)
-    match result.last() {
-        Some(Token { typ: NEWLINE, .. }) => (),
-        _ => result.push(Token {
-            typ: NEWLINE,
-            string: empty_end_slice,
-            span: Span::new(input_len, input_len),
-            line: empty_end_slice,
-        }),
-    }
-    // Emit any necessary dedents
-    for _ in indents.iter().skip(1) {
-        result.push(Token {
-            typ: DEDENT,
+        // Ensure content tokens end with newline (this allows parsers to be defined
+        // more consistently)
+        if self.last_typ != Some(NEWLINE) {
+            self.push(Token {
+                typ: NEWLINE,
+                string: empty_end_slice,
+                span: Span::new(input_len, input_len),
+                line: empty_end_slice,
+            })
+        }
+        // Emit any necessary dedents
+        for _ in 1..self.indents.len() {
+            self.push(Token {
+                typ: DEDENT,
+                string: empty_end_slice,
+                span: Span::new(input_len, input_len),
+                line: empty_end_slice,
+            });
+        }
+        self.push(Token {
+            typ: ENDMARKER,
             string: empty_end_slice,
             span: Span::new(input_len, input_len),
             line: empty_end_slice,
         });
     }
-    result.push(Token {
-        typ: ENDMARKER,
-        string: empty_end_slice,
-        span: Span::new(input_len, input_len),
-        line: empty_end_slice,
-    });
-
-    Ok(result)
 }
 
 #[cfg(test)]
@@ -420,6 +789,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wrapping_operators() {
+        for op in &["+%", "-%", "*%"] {
+            let input = format!("a {} b", op);
+            let tokens = tokenize(&input).unwrap();
+
+            assert_eq!(tokens[1].typ, OP);
+            assert_eq!(tokens[1].string, *op);
+        }
+    }
+
+    #[test]
+    fn test_radix_prefixed_numbers() {
+        let kinds = |input| {
+            tokenize(input)
+                .unwrap()
+                .into_iter()
+                .filter(|tok| tok.typ == NUMBER || tok.typ == ERRORTOKEN)
+                .map(|tok| (tok.typ, tok.string, tok.span))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(kinds("0b1010"), vec![(NUMBER, "0b1010", Span::new(0, 6))]);
+        assert_eq!(kinds("0o755"), vec![(NUMBER, "0o755", Span::new(0, 5))]);
+        assert_eq!(kinds("0B1_0"), vec![(NUMBER, "0B1_0", Span::new(0, 5))]);
+        assert_eq!(kinds("x = 0x1f"), vec![(NUMBER, "0x1f", Span::new(4, 8))]);
+
+        assert_eq!(
+            kinds("0b102"),
+            vec![
+                (NUMBER, "0b10", Span::new(0, 4)),
+                (ERRORTOKEN, "2", Span::new(4, 5)),
+            ]
+        );
+        assert_eq!(
+            kinds("x = 0o78 + 1"),
+            vec![
+                (NUMBER, "0o7", Span::new(4, 7)),
+                (ERRORTOKEN, "8", Span::new(7, 8)),
+                (NUMBER, "1", Span::new(11, 12)),
+            ]
+        );
+        assert_eq!(kinds("0b2"), vec![(ERRORTOKEN, "0b2", Span::new(0, 3))]);
+        assert_eq!(kinds("0o"), vec![(ERRORTOKEN, "0o", Span::new(0, 2))]);
+    }
+
     #[test]
     fn test_unicode_token() {
         let uni = "\u{6dd}";
@@ -3,6 +3,7 @@ use serde::{
     Serialize,
 };
 
+use crate::keywords::is_hard_keyword;
 use crate::span::{
     Span,
     Spanned,
@@ -27,16 +28,55 @@ pub enum TokenType {
     ERRORTOKEN,
 }
 
+impl TokenType {
+    /// A description of tokens of this type for use in error messages.
+    pub fn description(&self) -> &'static str {
+        match self {
+            TokenType::NAME => "name",
+            TokenType::NUMBER => "number literal",
+            TokenType::STRING => "string literal",
+            TokenType::OP => "operator",
+            TokenType::COMMENT => "comment",
+            TokenType::INDENT => "indent",
+            TokenType::DEDENT => "dedent",
+            TokenType::NEWLINE | TokenType::NL => "newline",
+            TokenType::ENDMARKER => "end of file",
+            TokenType::ERRORTOKEN => "invalid token",
+        }
+    }
+}
+
+/// A coarse category of tokens used when reporting source metrics.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum TokenKindCategory {
+    /// Names that are hard keywords
+    Keyword,
+    /// Names that are not keywords
+    Name,
+    Operator,
+    /// Number and string literals
+    Literal,
+    /// Comments and non-significant newlines
+    Trivia,
+    /// Significant newlines, indents, dedents, and the end marker
+    Layout,
+    Error,
+}
+
 /// A token parsed from a source string.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
 pub struct Token<'a> {
     /// The type of a token.
     pub typ: TokenType,
 
-    /// The text content of a parsed token.
+    /// The text content of a parsed token.  This is always the slice of the
+    /// source covered by `span`, so virtual tokens such as dedents and the end
+    /// marker have empty text.
     pub string: &'a str,
 
-    /// The span of source text covered by a token.
+    /// The span of source text covered by a token.  The spans of `NEWLINE`
+    /// and `NL` tokens cover the whole line terminator, so they are two bytes
+    /// long for "\r\n" and one byte long for "\n".
     pub span: Span,
 
     /// The text content of the line from which a token was parsed.
@@ -57,3 +97,82 @@ impl<'a> From<&Token<'a>> for Spanned<&'a str> {
         }
     }
 }
+
+impl<'a> Token<'a> {
+    pub fn is_keyword(&self) -> bool {
+        self.typ == TokenType::NAME && is_hard_keyword(self.string)
+    }
+
+    pub fn is_literal(&self) -> bool {
+        self.typ == TokenType::NUMBER || self.typ == TokenType::STRING
+    }
+
+    /// Return the prefix of a string literal e.g. "rb" in `rb"x"`.  The
+    /// prefix of unprefixed strings and of other tokens is empty.
+    pub fn string_prefix(&self) -> &'a str {
+        if self.typ != TokenType::STRING {
+            return "";
+        }
+
+        let end = self.string.find(&['"', '\''][..]).unwrap_or(0);
+        &self.string[..end]
+    }
+
+    /// Return true if this token is a byte string literal e.g. `b"deadbeef"`.
+    pub fn is_bytes(&self) -> bool {
+        self.string_prefix().contains(&['b', 'B'][..])
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        self.typ == TokenType::COMMENT || self.typ == TokenType::NL
+    }
+
+    pub fn is_layout(&self) -> bool {
+        matches!(
+            self.typ,
+            TokenType::NEWLINE | TokenType::INDENT | TokenType::DEDENT | TokenType::ENDMARKER
+        )
+    }
+
+    /// Return the category to which this token belongs.
+    pub fn category(&self) -> TokenKindCategory {
+        if self.is_keyword() {
+            TokenKindCategory::Keyword
+        } else if self.is_literal() {
+            TokenKindCategory::Literal
+        } else if self.is_trivia() {
+            TokenKindCategory::Trivia
+        } else if self.is_layout() {
+            TokenKindCategory::Layout
+        } else {
+            match self.typ {
+                TokenType::NAME => TokenKindCategory::Name,
+                TokenType::OP => TokenKindCategory::Operator,
+                _ => TokenKindCategory::Error,
+            }
+        }
+    }
+
+    /// Return true if this token has the same type and text as `other`,
+    /// regardless of where either token is located in the source.
+    pub fn same_kind(&self, other: &Token) -> bool {
+        self.typ == other.typ && self.string == other.string
+    }
+}
+
+/// Return true if two token streams contain the same kinds of tokens in the
+/// same order, ignoring their spans.
+pub fn streams_equivalent(a: &[Token], b: &[Token]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.same_kind(b))
+}
+
+/// Return the first token in `tokens` whose text in `source` equals `needle`.
+pub fn find_token_by_text<'a>(
+    tokens: &'a [Token],
+    source: &str,
+    needle: &str,
+) -> Option<&'a Token<'a>> {
+    tokens
+        .iter()
+        .find(|tok| source.get(tok.span.start..tok.span.end) == Some(needle))
+}
@@ -5,22 +5,47 @@ mod utils;
 
 use wasm_bindgen_test::wasm_bindgen_test;
 
-use fe_parser::ast::Module;
+use fe_parser::ast::{
+    BinOperator,
+    Expr,
+    FromImportNames,
+    FromImportPath,
+    FuncStmt,
+    Module,
+    ModuleStmt,
+    TypeDesc,
+};
 use fe_parser::builders::{
     many0,
     many1,
     pair,
     terminated,
 };
-use fe_parser::errors::ParseError;
+use fe_parser::errors::{
+    render_error,
+    Diagnostic,
+    ErrorKind,
+    ParseError,
+    Suggestion,
+};
+use fe_parser::keywords::{
+    Keywords,
+    ReservedWordWarning,
+};
 use fe_parser::parsers::*;
 use fe_parser::span::{
     Span,
     Spanned,
 };
+use fe_parser::tokenizer::TokenType;
 use fe_parser::{
     get_parse_tokens,
+    get_parse_tokens_with_trivia,
+    parse_contract,
+    parse_module,
+    to_json,
     Cursor,
+    Parse,
     ParseResult,
 };
 use utils::to_ron_string_pretty;
@@ -275,6 +300,923 @@ fn test_arr_dim_err() {
     );
 }
 
+#[test]
+#[wasm_bindgen_test]
+fn test_leading_dot_outside_parens_err() {
+    let src = "x\n.f()";
+    let toks = get_parse_tokens(src).unwrap();
+
+    // The newline terminates the statement before the leading dot
+    let (rest, _) = func_stmt(&toks).unwrap();
+    assert_eq!(rest, &toks[2..]);
+    assert!(func_stmt(rest).is_err());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_func_def_const_generic_err() {
+    let src = "def sum<const N>(a: u256[N]):\n    pass";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        func_def(&toks),
+        Err(ParseError::str(
+            &toks[5..],
+            "const generic parameter \"N\" requires a type annotation",
+        )),
+    );
+
+    let src = "def sum<T>(a: u256[T]):\n    pass";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        func_def(&toks),
+        Err(ParseError::str(
+            &toks[8..],
+            "array dimension \"T\" is not a const generic parameter",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_field_generic_dims_err() {
+    let expected_msg = "array dimension \"N\" is not a const generic parameter";
+
+    let src = "x: u256[N]\n";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        contract_field(&toks),
+        Err(ParseError::str(&toks[2..], expected_msg)),
+    );
+    assert_eq!(
+        struct_field(&toks),
+        Err(ParseError::str(&toks[2..], expected_msg)),
+    );
+    assert_eq!(
+        event_field(&toks),
+        Err(ParseError::str(&toks[2..], expected_msg)),
+    );
+
+    let src = "type Arr = map<address, u256[N]>\n";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        type_def(&toks),
+        Err(ParseError::str(&toks[7..], expected_msg)),
+    );
+
+    let src = "const X: u256[N] = [1, 2]\n";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        const_def(&toks),
+        Err(ParseError::str(&toks[3..], expected_msg)),
+    );
+
+    let src = "x: u256[N] = [1, 2]";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        vardecl_stmt(&toks),
+        Err(ParseError::str(&toks[2..], expected_msg)),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_func_def_constructor_return_type_err() {
+    let src = "pub def __init__() -> u256:\n    return 1";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        func_def(&toks),
+        Err(ParseError::static_str(
+            &toks[5..],
+            "constructor \"__init__\" cannot declare a return type",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_yul_stmt() {
+    let src =
+        "x = 1\nyul:\n    let y := add(x, 1)\n    if y {\n        sstore(0, y)\n    }\nz = 2\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, stmts) = terminated(many1(func_stmt), endmarker_token)(&toks).unwrap();
+    let stmts: Vec<_> = stmts.into_iter().flatten().collect();
+
+    assert_eq!(stmts.len(), 3);
+    assert!(matches!(stmts[0].node, FuncStmt::Assign { .. }));
+    assert!(matches!(stmts[2].node, FuncStmt::Assign { .. }));
+
+    let source_span = Span::new(15, 71);
+    assert_eq!(
+        stmts[1],
+        Spanned {
+            node: FuncStmt::Yul { source_span },
+            span: Span::new(6, 71),
+        },
+    );
+    assert_eq!(
+        &src[source_span.start..source_span.end],
+        "let y := add(x, 1)\n    if y {\n        sstore(0, y)\n    }",
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_int_type_width_err() {
+    let src = "x: uint<0>";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        arg_def(&toks),
+        Err(ParseError::str(
+            &toks[4..],
+            "integer width \"0\" must be between 1 and 256 bits",
+        )),
+    );
+
+    let src = "x: int<257>";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        arg_def(&toks),
+        Err(ParseError::str(
+            &toks[4..],
+            "integer width \"257\" must be between 1 and 256 bits",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_current_span() {
+    let toks = get_parse_tokens("foo + bar").unwrap();
+
+    assert_eq!(current_span(&toks), Span::new(0, 3));
+    assert_eq!(current_span(&toks[1..]), Span::new(4, 5));
+
+    let eof = &toks[toks.len() - 1..];
+    assert_eq!(eof[0].typ, TokenType::ENDMARKER);
+    assert_eq!(current_span(eof), Span::new(9, 9));
+    assert_eq!(current_span(&toks[toks.len()..]), Span::new(0, 0));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_array_repeat_mixed_separators_err() {
+    let expected_msg = "cannot mix \";\" and \",\" in an array literal";
+
+    let src = "[1, 2; 3]";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        list(&toks),
+        Err(ParseError::static_str(&toks[4..], expected_msg))
+    );
+
+    let src = "[1; 2, 3]";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        list(&toks),
+        Err(ParseError::static_str(&toks[4..], expected_msg))
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_nested_list() {
+    // Each element is parsed once, so deeply nested literals parse quickly.
+    let depth = 24;
+    let src = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+    let toks = get_parse_tokens(&src).unwrap();
+    let (rest, mut exp) = list(&toks).unwrap();
+    assert_eq!(rest[0].typ, TokenType::NEWLINE);
+
+    for _ in 1..depth {
+        exp = match exp.node {
+            Expr::List { mut elts } if elts.len() == 1 => elts.pop().unwrap(),
+            _ => panic!("expected a nested list"),
+        };
+    }
+    assert_eq!(exp.span, Span::new(depth - 1, depth + 2));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_named_returns_mixed_err() {
+    let expected_msg = "cannot mix named and unnamed return values";
+
+    let src = "def foo() -> (total: u256, bool):\n    pass";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        func_def(&toks),
+        Err(ParseError::static_str(&toks[10..], expected_msg)),
+    );
+
+    let src = "def foo() -> (u256, ok: bool):\n    pass";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        func_def(&toks),
+        Err(ParseError::static_str(&toks[8..], expected_msg)),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_return_spec_parenthesized() {
+    let toks = get_parse_tokens("(u256)").unwrap();
+    let (_, (named, typ)) = return_spec(&toks).unwrap();
+    assert!(named.is_empty());
+    assert_eq!(typ.node, TypeDesc::Base { base: "u256" });
+
+    let toks = get_parse_tokens("(u256,)").unwrap();
+    let (_, (named, typ)) = return_spec(&toks).unwrap();
+    assert!(named.is_empty());
+    assert_eq!(
+        typ.node,
+        TypeDesc::Tuple {
+            items: vec![Spanned {
+                node: TypeDesc::Base { base: "u256" },
+                span: Span::new(1, 5),
+            }],
+        },
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_never_type_non_return_err() {
+    let expected_msg = "the \"never\" type may only be used as a return type";
+
+    let src = "x: never";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        arg_def(&toks),
+        Err(ParseError::static_str(&toks[2..], expected_msg)),
+    );
+
+    let src = "x: map<address, never>";
+    let toks = get_parse_tokens(src).unwrap();
+    assert!(standalone(type_desc)(&toks[2..]).is_err());
+
+    let src = "x: never[2] = 0";
+    let toks = get_parse_tokens(src).unwrap();
+    assert!(standalone(vardecl_stmt)(&toks).is_err());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_module_stmt_iterators() {
+    let src = r#"import foo
+from bar import baz
+type Addr = address
+static_assert(true)
+
+contract Foo:
+    x: u256
+
+struct Bar:
+    y: u256
+
+struct Baz
+
+const MAX: u256 = 10
+
+interface Token:
+    def total_supply() -> u256
+"#;
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+    let module = module.node;
+
+    let names = |stmts: Vec<&Spanned<ModuleStmt>>| -> Vec<String> {
+        stmts
+            .iter()
+            .map(|stmt| match &stmt.node {
+                ModuleStmt::StructDef { name, .. }
+                | ModuleStmt::ContractDef { name, .. }
+                | ModuleStmt::TypeDef { name, .. }
+                | ModuleStmt::ConstDef { name, .. }
+                | ModuleStmt::InterfaceDef { name, .. } => name.node.to_string(),
+                ModuleStmt::SimpleImport { .. } => "import".to_string(),
+                ModuleStmt::FromImport { .. } => "from".to_string(),
+                _ => panic!("unexpected statement"),
+            })
+            .collect()
+    };
+
+    assert_eq!(names(module.structs().collect()), vec!["Bar", "Baz"]);
+    assert_eq!(names(module.contracts().collect()), vec!["Foo"]);
+    assert_eq!(names(module.type_aliases().collect()), vec!["Addr"]);
+    assert_eq!(names(module.imports().collect()), vec!["import", "from"]);
+    assert_eq!(names(module.consts().collect()), vec!["MAX"]);
+    assert_eq!(names(module.interfaces().collect()), vec!["Token"]);
+
+    // Items are borrowed from the module body
+    let first_struct = module.structs().next().unwrap();
+    assert!(std::ptr::eq(first_struct, &module.body[5]));
+
+    let asserts: Vec<_> = module
+        .filter_map_stmts(|stmt| match &stmt.node {
+            ModuleStmt::StaticAssert { cond, .. } => Some(cond),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(asserts.len(), 1);
+    assert_eq!(asserts[0].node, Expr::Bool(true));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_docstrings() {
+    let src = r#""""Token contracts."""
+
+contract Token:
+    "A simple \"token\"."
+    balance: u256
+
+    pub def mint(x: u256):
+        'Mint some tokens.\n'
+        self.balance += x
+
+    pub def burn(x: u256):
+        self.balance -= x
+"#;
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+    let module = module.node;
+
+    assert_eq!(module.doc(), Some("Token contracts.".to_string()));
+    assert_eq!(module.body.len(), 2);
+
+    let contract = &module.body[1].node;
+    assert_eq!(contract.doc(), Some("A simple \"token\".".to_string()));
+
+    let funcs = match contract {
+        ModuleStmt::ContractDef { body, .. } => body,
+        _ => panic!("expected a contract definition"),
+    };
+    assert_eq!(funcs[2].node.doc(), Some("Mint some tokens.\n".to_string()));
+    assert_eq!(funcs[3].node.doc(), None);
+    assert_eq!(funcs[1].node.doc(), None);
+
+    let src = "contract Token:\n    balance: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+
+    assert_eq!(module.node.doc(), None);
+    assert_eq!(module.node.body[0].node.doc(), None);
+
+    let src = "event Minted:\n    \"Tokens were minted.\"\n    value: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, event) = event_def(&toks).unwrap();
+
+    assert_eq!(event.node.doc(), Some("Tokens were minted.".to_string()));
+
+    let src = "event Burned:\n    value: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, event) = event_def(&toks).unwrap();
+
+    assert_eq!(event.node.doc(), None);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_contract_file_input() {
+    let src = "contract Foo:\n    x: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (rest, contract) = contract_file_input(&toks).unwrap();
+
+    assert!(rest.is_empty());
+    assert!(matches!(
+        contract.node,
+        ModuleStmt::ContractDef { name, .. } if name.node == "Foo"
+    ));
+
+    let src = "contract Foo:\n    x: u256\nstruct Bar:\n    y: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(
+        contract_file_input(&toks),
+        Err(ParseError::static_str(
+            &toks[10..],
+            "expected a single contract"
+        )),
+    );
+
+    let src = "";
+    let toks = get_parse_tokens(src).unwrap();
+    assert!(contract_file_input(&toks).is_err());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_parse_contract() {
+    let contract = parse_contract("contract Foo:\n    x: u256\n").unwrap();
+    assert!(matches!(
+        contract.node,
+        ModuleStmt::ContractDef { name, .. } if name.node == "Foo"
+    ));
+    assert_eq!(contract.span, Span::new(0, 25));
+
+    let error =
+        parse_contract("contract Foo:\n    x: u256\nstruct Bar:\n    y: u256\n").unwrap_err();
+    assert_eq!(error.message, "expected a single contract");
+    assert_eq!(error.span, Span::new(26, 32));
+
+    assert!(parse_contract("").is_err());
+
+    // Tokenizer errors are reported as diagnostics too
+    let src = "contract Foo:\n    x: u256\n  y: u256\n";
+    let error = parse_contract(src).unwrap_err();
+    assert_eq!(error, Diagnostic::from(get_parse_tokens(src).unwrap_err()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_struct_def_marker_body_err() {
+    let src = "struct Marker\n    x: u256";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        struct_def(&toks),
+        Err(ParseError::str(
+            &toks[3..],
+            "marker struct \"Marker\" cannot have a body; add \":\" after the name to define fields",
+        )
+        .with_suggestion(Suggestion::insert(13, ":"))),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_missing_block_colon_suggestion() {
+    let src = "def foo(x: u256)\n    pass";
+    let toks = get_parse_tokens(src).unwrap();
+    let err = func_def(&toks).unwrap_err();
+
+    assert_eq!(err.suggestions(), &[Suggestion::insert(16, ":")]);
+
+    let src = "contract Foo\n    x: u256";
+    let toks = get_parse_tokens(src).unwrap();
+    let err = contract_def(&toks).unwrap_err();
+
+    assert_eq!(
+        err.suggestions(),
+        &[Suggestion {
+            span: Span::new(12, 12),
+            replacement: ":".to_string(),
+        }],
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_contract_def_empty_body_err() {
+    let expected_msg = "contract \"Foo\" has no body; add at least one member";
+
+    let src = "contract Foo:\n";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        contract_def(&toks),
+        Err(ParseError::str(&toks[1..], expected_msg)),
+    );
+    assert_eq!(toks[1].span, Span::new(9, 12));
+
+    let src = "contract Foo:\ncontract Bar:\n    x: u256";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        contract_def(&toks),
+        Err(ParseError::str(&toks[1..], expected_msg)),
+    );
+
+    // the input ends right after the indent
+    let src = "contract Foo:\n    x: u256";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(toks[4].typ, TokenType::INDENT);
+
+    assert_eq!(contract_def(&toks[..5]), Err(ParseError::eof(&toks[5..5])));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_contract_def_duplicate_constructor_err() {
+    let src = r#"contract Foo:
+    pub def __init__():
+        pass
+    pub def bar():
+        pass
+    def __init__():
+        pass"#;
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        contract_def(&toks),
+        Err(ParseError::static_str(
+            &toks[27..],
+            "duplicate definition of constructor \"__init__\"",
+        )),
+    );
+    assert_eq!(toks[27].string, "def");
+    assert_eq!(toks[28].string, "__init__");
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_validate_import() {
+    for src in &[
+        "from .foo import bar",
+        "from foo.bar import baz",
+        "import foo.bar as baz",
+    ] {
+        let toks = get_parse_tokens(src).unwrap();
+        let (_, stmt) = import_stmt(&toks).unwrap();
+
+        assert_eq!(validate_import(&toks, &stmt.node), Ok(()));
+    }
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_validate_module_order() {
+    let src = r#""""docs"""
+import foo
+from bar import baz
+export Foo
+
+contract Foo:
+    x: u256
+
+struct Bar:
+    y: u256
+"#;
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+    assert_eq!(validate_module_order(&toks, &module.node), Ok(()));
+
+    let src = "import foo\ncontract Foo:\n    x: u256\nimport bar\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+    assert_eq!(toks[13].string, "import");
+    assert_eq!(
+        validate_module_order(&toks, &module.node),
+        Err(ParseError::static_str(
+            &toks[13..],
+            "import statements must precede definitions",
+        )),
+    );
+
+    let src = "type Foo = u256\nexport Foo\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, module) = file_input(&toks).unwrap();
+    assert_eq!(
+        validate_module_order(&toks, &module.node),
+        Err(ParseError::static_str(
+            &toks[5..],
+            "export statements must precede definitions",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_validate_import_err() {
+    let src = "from foo import bar";
+    let toks = get_parse_tokens(src).unwrap();
+
+    let empty_path = ModuleStmt::FromImport {
+        path: Spanned {
+            node: FromImportPath::Absolute { path: vec![] },
+            span: Span::new(5, 5),
+        },
+        names: Spanned {
+            node: FromImportNames::Star,
+            span: Span::new(13, 14),
+        },
+    };
+    assert_eq!(
+        validate_import(&toks, &empty_path),
+        Err(ParseError::static_str(
+            &toks[..],
+            "absolute import path must not be empty",
+        )),
+    );
+
+    let src = "from ........... import bar";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        import_stmt(&toks),
+        Err(ParseError::str(
+            &toks[..],
+            "relative import ascends more than 8 parent levels",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_hard_keyword_identifier_err() {
+    let src = "while = 1";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        name_token(&toks),
+        Err(ParseError::str(
+            &toks[..],
+            "keyword \"while\" cannot be used as an identifier",
+        )),
+    );
+    assert!(standalone(small_stmt)(&toks).is_err());
+
+    // Soft keywords are identifiers outside of the positions that use them
+    let src = "from = 1";
+    let toks = get_parse_tokens(src).unwrap();
+    assert!(standalone(small_stmt)(&toks).is_ok());
+    assert_eq!(Keywords::default().reserved_warnings(&toks), vec![]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_reserved_word_identifier_warns() {
+    let src = "match = 1";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert!(standalone(small_stmt)(&toks).is_ok());
+
+    let warnings = Keywords::default().reserved_warnings(&toks);
+    assert_eq!(
+        warnings,
+        vec![ReservedWordWarning {
+            word: "match",
+            span: Span::new(0, 5),
+        }],
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_parse_from_source() {
+    let src = "contract Foo:\n    x: u256\n";
+    let parse = Parse::from_source(src);
+    assert_eq!(parse.diagnostics, vec![]);
+    assert_eq!(parse.module, file_input(&parse.tokens).unwrap().1);
+
+    let src = "contract Foo:\n    x y\n    z: u256\nstruct Bar:\n    x: u256\n";
+    let parse = Parse::from_source(src);
+    assert_eq!(parse.diagnostics.len(), 1);
+    assert_eq!(parse.diagnostics[0].span, Span::new(18, 19));
+
+    let module = &parse.module;
+    assert_eq!(module.node.structs().count(), 1);
+    assert_eq!(module.node.contracts().count(), 0);
+    assert_eq!(module.span, Span::new(34, 57));
+
+    let parse = Parse::from_source("x = 1)");
+    assert!(!parse.tokens.is_empty());
+    assert_eq!(parse.diagnostics[0].message, "Unbalanced brackets");
+    assert_eq!(parse.diagnostics[0].span, Span::new(6, 6));
+    assert_eq!(parse.module.node.body, vec![]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_parse_module() {
+    let src = "import foo\ncontract Foo:\n    x: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    assert_eq!(parse_module(src), Ok(file_input(&toks).unwrap().1));
+
+    let src = "contract Foo:\n    x: u256\nfoo bar\n";
+    let error = parse_module(src).unwrap_err();
+    assert_eq!(error.span, Span::new(26, 29));
+
+    let src = "contract Foo:\n    x: u256\nimport foo\n";
+    let error = parse_module(src).unwrap_err();
+    assert_eq!(error.message, "import statements must precede definitions");
+    assert_eq!(error.span, Span::new(26, 32));
+
+    // tokenizer errors are reported as diagnostics too
+    let src = "contract Foo:\n    x: u256\n  y: u256\n";
+    let error = parse_module(src).unwrap_err();
+    assert_eq!(error, Diagnostic::from(get_parse_tokens(src).unwrap_err()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_render_error() {
+    let src = "contract Foo:\n    x: u256\n    y u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let error = Diagnostic::from(&contract_def(&toks).unwrap_err());
+    let rendered = render_error(src, &error);
+    let lines: Vec<_> = rendered.lines().collect();
+
+    assert_eq!(lines[0], format!("at line 3 col 4, {}:", error.message));
+    assert_eq!(lines[1], "    y u256");
+    assert_eq!(lines[2], "    ^");
+
+    let error = Diagnostic {
+        message: "unknown type".to_string(),
+        span: Span::new(21, 25),
+    };
+    assert_eq!(
+        render_error(src, &error),
+        "at line 2 col 7, unknown type:\n    x: u256\n       ^^^^\n",
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_get_parse_tokens_with_trivia() {
+    let src = "# first\ncontract Foo:\n    # second\n    x: u256\n";
+    let (toks, trivia) = get_parse_tokens_with_trivia(src).unwrap();
+    assert_eq!(toks, get_parse_tokens(src).unwrap());
+
+    let comments: Vec<_> = trivia
+        .iter()
+        .filter(|tok| tok.typ == TokenType::COMMENT)
+        .map(|tok| (tok.string, tok.span))
+        .collect();
+    assert_eq!(
+        comments,
+        vec![
+            ("# first", Span::new(0, 7)),
+            ("# second", Span::new(26, 34))
+        ],
+    );
+    assert!(trivia
+        .iter()
+        .all(|tok| tok.typ == TokenType::COMMENT || tok.typ == TokenType::NL));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_to_json() {
+    let src = "contract Foo:\n    event Bar:\n        idx sender: address\n        value: u256\n";
+    let module = parse_module(src).unwrap();
+    let json = to_json(&module);
+
+    assert!(json.contains(r#""name":{"node":"Foo","span":{"start":9,"end":12}}"#));
+    assert!(json.contains(r#""node":"sender""#));
+    assert!(json.contains(r#""node":"value""#));
+
+    let parsed: Spanned<Module> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, module);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_relative_import_dots() {
+    for (src, expected) in [
+        ("from . import a", 1),
+        ("from .. import b", 2),
+        ("from ...pkg import c", 3),
+        ("from ....pkg import d", 4),
+    ]
+    .iter()
+    {
+        let toks = get_parse_tokens(src).unwrap();
+        let stmt = from_import(&toks).unwrap().1;
+
+        match stmt.node {
+            ModuleStmt::FromImport { path, .. } => match path.node {
+                FromImportPath::Relative { dots, .. } => assert_eq!(dots, *expected, "{}", src),
+                _ => panic!("expected a relative path in {:?}", src),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_from_import_names_parens_eof() {
+    let src = "from a import (b)";
+    let toks = get_parse_tokens(src).unwrap();
+
+    // the input ends right after the opening paren
+    assert_eq!(
+        from_import_names_parens(&toks[3..4]),
+        Err(ParseError::eof(&toks[4..4])),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_event_def_duplicate_field_err() {
+    let src = "event Transfer:\n    amount: u256\n    idx to: address\n    amount: u256\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let err = event_def(&toks).unwrap_err();
+
+    assert_eq!(
+        err,
+        ParseError::str(&toks[14..], "duplicate event field \"amount\""),
+    );
+    assert_eq!(toks[14].span, Span::new(57, 63));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_const_def_value() {
+    let toks = get_parse_tokens("const MAX: u256 = 2 ** 8\n").unwrap();
+    let (_, stmt) = const_def(&toks).unwrap();
+
+    match stmt.node {
+        ModuleStmt::ConstDef { name, typ, value } => {
+            assert_eq!(name.node, "MAX");
+            assert_eq!(typ.node, TypeDesc::Base { base: "u256" });
+            assert!(matches!(
+                value.node,
+                Expr::BinOperation {
+                    op: Spanned {
+                        node: BinOperator::Pow,
+                        ..
+                    },
+                    ..
+                }
+            ));
+            assert_eq!(value.span, Span::new(18, 24));
+        }
+        _ => panic!("expected a constant definition"),
+    }
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_contract_def_base() {
+    let base = |src: &str| -> Vec<String> {
+        let toks = get_parse_tokens(src).unwrap();
+        match contract_def(&toks).unwrap().1.node {
+            ModuleStmt::ContractDef { base, .. } => {
+                base.iter().map(|base| base.node.to_string()).collect()
+            }
+            _ => panic!("expected a contract definition"),
+        }
+    };
+
+    assert!(base("contract A:\n    x: u256\n").is_empty());
+    assert_eq!(base("contract A(B, C):\n    x: u256\n"), vec!["B", "C"]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_interface_def_body_err() {
+    let src = "interface Token:\n    def total_supply() -> u256:\n        return 0\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let error = Diagnostic::from(&interface_def(&toks).unwrap_err());
+
+    assert_eq!(
+        error.message,
+        "interface function \"total_supply\" cannot have a body",
+    );
+    assert_eq!(error.span, Span::new(47, 48));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_func_sig_named_returns_err() {
+    let src = "def balance(owner: address) -> (total: u256, ok: bool)\n";
+    let toks = get_parse_tokens(src).unwrap();
+
+    assert_eq!(
+        func_sig(&toks),
+        Err(ParseError::str(
+            &toks[7..],
+            "interface function \"balance\" cannot have named return values",
+        )),
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_module_stmt_unexpected_token() {
+    let src = "contract Foo:\n    x: u256\n42\n";
+    let toks = get_parse_tokens(src).unwrap();
+    let (_, errors) = resilient_file_input(&toks);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].kind(),
+        &ErrorKind::UnexpectedToken {
+            found: TokenType::NUMBER,
+            expected: MODULE_STMT_KEYWORDS.to_vec(),
+        },
+    );
+
+    let error = parse_module(src).unwrap_err();
+    assert_eq!(
+        error.message,
+        "expected 'import', 'from', 'export', 'type', 'const', 'static_assert', 'pub', \
+         'contract', 'interface' or 'struct', found number literal",
+    );
+    assert_eq!(error.span, Span::new(26, 28));
+
+    let src = "interface Token:\n    def total_supply() -> u256:\n        return 0\n";
+    let error = parse_module(src).unwrap_err();
+    assert_eq!(
+        error.message,
+        "interface function \"total_supply\" cannot have a body",
+    );
+}
+
 // Uncomment this to update all fixtures.
 //#[test]
 //fn write_fixtures() {
@@ -300,6 +1242,12 @@ parser_fixture_tests! {
         write_module_stmt,
         "fixtures/parsers/module_stmt.ron",
     ),
+    (
+        repeat(export_stmt),
+        test_export_stmt,
+        write_export_stmt,
+        "fixtures/parsers/export_stmt.ron",
+    ),
     (
         repeat(import_stmt),
         test_import_stmt,
@@ -420,6 +1368,18 @@ parser_fixture_tests! {
         write_type_def,
         "fixtures/parsers/type_def.ron",
     ),
+    (
+        repeat(interface_def),
+        test_interface_def,
+        write_interface_def,
+        "fixtures/parsers/interface_def.ron",
+    ),
+    (
+        repeat(const_def),
+        test_const_def,
+        write_const_def,
+        "fixtures/parsers/const_def.ron",
+    ),
     (
         repeat_newline(type_desc),
         test_type_desc,
@@ -570,6 +1530,12 @@ parser_fixture_tests! {
         write_while_stmt,
         "fixtures/parsers/while_stmt.ron",
     ),
+    (
+        repeat(unchecked_stmt),
+        test_unchecked_stmt,
+        write_unchecked_stmt,
+        "fixtures/parsers/unchecked_stmt.ron",
+    ),
     (
         repeat_newline(exprs),
         test_exprs,
@@ -642,6 +1608,12 @@ parser_fixture_tests! {
         write_term,
         "fixtures/parsers/term.ron",
     ),
+    (
+        repeat_newline(cast),
+        test_cast,
+        write_cast,
+        "fixtures/parsers/cast.ron",
+    ),
     (
         repeat_newline(factor),
         test_factor,
@@ -6,12 +6,21 @@ mod utils;
 use serde::Serialize;
 use wasm_bindgen_test::wasm_bindgen_test;
 
+use fe_parser::span::Span;
 use fe_parser::string_utils::StringPositions;
+use fe_parser::token_histogram;
 use fe_parser::tokenizer::{
+    find_token_by_text,
+    streams_equivalent,
+    token_iter,
     tokenize,
+    tokenize_lossy,
+    tokenize_with_options,
     Token,
+    TokenKindCategory,
     TokenType,
     TokenizeError,
+    TokenizeOptions,
 };
 
 /// A python token object similar to those defined in python's stdlib `tokenize`
@@ -102,13 +111,26 @@ event Test:
             Err(TokenizeError {
                 msg: "unindent does not match any outer indentation level",
                 offset: 36,
+                line: 4,
+                col: 3,
             }),
         ),
         (
             r#"s = """"#,
             Err(TokenizeError {
-                msg: "EOF in multi-line string",
-                offset: 7,
+                msg: "unterminated multi-line string starting here",
+                offset: 4,
+                line: 1,
+                col: 4,
+            }),
+        ),
+        (
+            "x = 1\ns = \"\"\"foo\nbar\n",
+            Err(TokenizeError {
+                msg: "unterminated multi-line string starting here",
+                offset: 10,
+                line: 2,
+                col: 4,
             }),
         ),
         (
@@ -116,6 +138,26 @@ event Test:
             Err(TokenizeError {
                 msg: "EOF in multi-line statement",
                 offset: 10,
+                line: 2,
+                col: 0,
+            }),
+        ),
+        (
+            r#"s = f"x""#,
+            Err(TokenizeError {
+                msg: "formatted string literals are not supported",
+                offset: 4,
+                line: 1,
+                col: 4,
+            }),
+        ),
+        (
+            "x = 1\ns = Rf\"\"\"{x}\n\"\"\"",
+            Err(TokenizeError {
+                msg: "formatted string literals are not supported",
+                offset: 10,
+                line: 2,
+                col: 4,
             }),
         ),
     ];
@@ -123,4 +165,351 @@ event Test:
     for (input, expected) in examples {
         assert_eq!(tokenize(input), expected);
     }
+
+    assert!(tokenize(r#"s = "x""#).is_ok());
+    assert!(tokenize(r#"s = b"x""#).is_ok());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_token_histogram() {
+    let src = "# comment\nif x == 0x10:\n    y = \"foo\"\n";
+    let histogram = token_histogram(src).unwrap();
+
+    let count = |category| histogram.get(&category).copied().unwrap_or(0);
+
+    assert_eq!(count(TokenKindCategory::Keyword), 1);
+    assert_eq!(count(TokenKindCategory::Name), 2);
+    assert_eq!(count(TokenKindCategory::Operator), 3);
+    assert_eq!(count(TokenKindCategory::Literal), 2);
+    assert_eq!(count(TokenKindCategory::Trivia), 2);
+    assert_eq!(count(TokenKindCategory::Layout), 5);
+    assert_eq!(count(TokenKindCategory::Error), 0);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_streams_equivalent() {
+    let a = tokenize("x = foo(1)\n").unwrap();
+    let b = tokenize("\n\nx   =  foo( 1 )\n").unwrap();
+    let c = tokenize("x = foo(2)\n").unwrap();
+    let d = tokenize("x = foo\n").unwrap();
+
+    fn significant<'a>(toks: &[Token<'a>]) -> Vec<Token<'a>> {
+        toks.iter()
+            .filter(|tok| !tok.is_trivia())
+            .copied()
+            .collect()
+    }
+
+    assert!(streams_equivalent(&significant(&a), &significant(&b)));
+    assert!(significant(&a)[0].same_kind(&significant(&b)[0]));
+    assert_ne!(significant(&a)[0], significant(&b)[0]);
+
+    assert!(!streams_equivalent(&a, &c));
+    assert!(!a[4].same_kind(&c[4]));
+    assert!(!streams_equivalent(&a, &d));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_token_text_matches_span() {
+    let src = "contract Foo:\n    def bar():\n        x = \"\"\"a\nb\"\"\"\n# done";
+    let toks = tokenize(src).unwrap();
+
+    for tok in &toks {
+        assert_eq!(tok.string, &src[tok.span.start..tok.span.end]);
+    }
+
+    let virtual_toks: Vec<_> = toks
+        .iter()
+        .filter(|tok| matches!(tok.typ, TokenType::DEDENT | TokenType::ENDMARKER))
+        .collect();
+    assert_eq!(virtual_toks.len(), 3);
+    assert!(virtual_toks.iter().all(|tok| tok.string.is_empty()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_find_token_by_text() {
+    let src = "def foo(balance: u256):\n    return balance\n";
+    let toks = tokenize(src).unwrap();
+
+    let balance = find_token_by_text(&toks, src, "balance").unwrap();
+    assert_eq!(balance.typ, TokenType::NAME);
+    assert_eq!(balance.span, Span::new(8, 15));
+
+    let return_kw = find_token_by_text(&toks, src, "return").unwrap();
+    assert_eq!(return_kw.typ, TokenType::NAME);
+    assert_eq!(return_kw.span, Span::new(28, 34));
+
+    assert_eq!(find_token_by_text(&toks, src, "missing"), None);
+    assert_eq!(find_token_by_text(&toks, src, "bal"), None);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_token_iter() {
+    let src = r#"
+"""Two contracts"""
+contract Foo:
+    x: map<address, u256>
+
+    pub def get(a: address) -> u256:
+        return self.x[a]  # look it up
+
+contract Bar:
+    foo: Foo
+    msg: string100 = """multi
+line"""
+
+    pub def call(
+        a: address,
+    ) -> u256:
+        return 1 + (2 *
+            3)
+"#;
+    let streamed: Result<Vec<_>, _> = token_iter(src).collect();
+    assert_eq!(streamed, tokenize(src));
+
+    // Tokens are produced before an error later in the input is found
+    let mut iter = token_iter("x = 1\ny = (\n");
+    assert_eq!(iter.next().unwrap().unwrap().string, "x");
+    assert!(token_iter("x = 1\ny = (\n").any(|tok| tok.is_err()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_newline_spans() {
+    let unix = "x = 1\n\ndef f():\n    # c\n    return (1,\n  2)\n";
+    let windows = unix.replace('\n', "\r\n");
+
+    let positions = |src| -> Vec<_> {
+        tokenize(src)
+            .unwrap()
+            .iter()
+            .map(|tok| (tok.typ, tok.span.line_col(src)))
+            .collect()
+    };
+    assert_eq!(positions(unix), positions(&windows));
+
+    // Newline tokens cover the whole line terminator
+    let newlines: Vec<_> = tokenize(&windows)
+        .unwrap()
+        .into_iter()
+        .filter(|tok| matches!(tok.typ, TokenType::NEWLINE | TokenType::NL))
+        .map(|tok| (tok.string, tok.span.end - tok.span.start))
+        .collect();
+    assert!(newlines.iter().all(|newline| *newline == ("\r\n", 2)));
+    assert_eq!(newlines.len(), 6);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_byte_string_tokens() {
+    let strings = |input| -> Vec<(TokenType, &str, bool)> {
+        tokenize(input)
+            .unwrap()
+            .into_iter()
+            .filter(|tok| tok.is_literal())
+            .map(|tok| (tok.typ, tok.string_prefix(), tok.is_bytes()))
+            .collect()
+    };
+
+    assert_eq!(strings(r#""x""#), vec![(TokenType::STRING, "", false)]);
+    assert_eq!(strings(r#"b"x""#), vec![(TokenType::STRING, "b", true)]);
+    assert_eq!(strings(r#"rb"x""#), vec![(TokenType::STRING, "rb", true)]);
+    assert_eq!(strings("R'''x'''"), vec![(TokenType::STRING, "R", false)]);
+    assert_eq!(strings("1"), vec![(TokenType::NUMBER, "", false)]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_float_literals() {
+    let toks: Vec<_> = tokenize("1.5 0.5 1e10 1.5e3 2.5E-3")
+        .unwrap()
+        .into_iter()
+        .filter(|tok| tok.typ == TokenType::NUMBER)
+        .map(|tok| tok.string)
+        .collect();
+
+    assert_eq!(toks, vec!["1.5", "0.5", "1e10", "1.5e3", "2.5E-3"]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_cast_modes() {
+    let kinds = |src| {
+        tokenize(src)
+            .unwrap()
+            .iter()
+            .map(|tok| (tok.typ, tok.string))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        kinds("x as! u8")[..4],
+        [
+            (TokenType::NAME, "x"),
+            (TokenType::NAME, "as"),
+            (TokenType::OP, "!"),
+            (TokenType::NAME, "u8"),
+        ]
+    );
+    assert_eq!(
+        kinds("x as? u8")[..4],
+        [
+            (TokenType::NAME, "x"),
+            (TokenType::NAME, "as"),
+            (TokenType::OP, "?"),
+            (TokenType::NAME, "u8"),
+        ]
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_max_string_len() {
+    let options = TokenizeOptions {
+        max_string_len: Some(8),
+        ..TokenizeOptions::default()
+    };
+    let too_long = |input, offset| {
+        Err(TokenizeError::new(
+            input,
+            "string literal exceeds the maximum length",
+            offset,
+        ))
+    };
+
+    // The limit includes the quotes
+    let input = "x = \"123456\"";
+    assert_eq!(tokenize_with_options(input, &options), tokenize(input));
+    let input = "x = \"1234567\"";
+    assert_eq!(tokenize_with_options(input, &options), too_long(input, 12));
+
+    // Multi-line strings are rejected on the first line past the limit, even
+    // if they are never terminated
+    let input = "x = \"\"\"1\n\"\"\"";
+    assert_eq!(tokenize_with_options(input, &options), tokenize(input));
+    let input = "x = \"\"\"12\n345\n6789\n";
+    assert_eq!(tokenize_with_options(input, &options), too_long(input, 12));
+    let input = "x = '12\\\n3456789'";
+    assert_eq!(tokenize_with_options(input, &options), too_long(input, 12));
+
+    let input = "x = \"1234567\"";
+    assert!(tokenize_with_options(input, &TokenizeOptions::default()).is_ok());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_lossy() {
+    let input = "x = \"oops\ncontract Foo:\n    y: u256\n";
+    let (toks, errors) = tokenize_lossy(input);
+
+    assert_eq!(
+        errors,
+        vec![TokenizeError::new(input, "unterminated string", 4)]
+    );
+    assert_eq!(toks[2].typ, TokenType::ERRORTOKEN);
+    assert_eq!(toks[2].string, "\"oops");
+
+    // The contract after the bad string is still tokenized
+    let names: Vec<_> = toks
+        .iter()
+        .filter(|tok| tok.typ == TokenType::NAME)
+        .map(|tok| tok.string)
+        .collect();
+    assert_eq!(names, vec!["x", "contract", "Foo", "y", "u256"]);
+    assert_eq!(toks.last().unwrap().typ, TokenType::ENDMARKER);
+
+    // Every error is collected, including ones that stop `tokenize`
+    let input = "x = 1)\ny = \"\"\"never closed\n";
+    let (toks, errors) = tokenize_lossy(input);
+    assert_eq!(tokenize(input).unwrap_err(), errors[0]);
+    assert_eq!(
+        errors.iter().map(|error| error.msg).collect::<Vec<_>>(),
+        vec![
+            "Unbalanced brackets",
+            "unterminated multi-line string starting here",
+        ]
+    );
+    let error_tok = toks
+        .iter()
+        .find(|tok| tok.typ == TokenType::ERRORTOKEN)
+        .unwrap();
+    assert_eq!(error_tok.span, Span::new(11, input.len()));
+
+    let input = "contract Foo:\n    y: u256\n";
+    assert_eq!(tokenize_lossy(input), (tokenize(input).unwrap(), vec![]));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_tab_size() {
+    let layout = |input, tab_size| {
+        let options = TokenizeOptions {
+            tab_size,
+            ..TokenizeOptions::default()
+        };
+
+        tokenize_with_options(input, &options)
+            .unwrap()
+            .into_iter()
+            .filter(|tok| matches!(tok.typ, TokenType::INDENT | TokenType::DEDENT))
+            .map(|tok| tok.typ)
+            .collect::<Vec<_>>()
+    };
+
+    // With 4 column tabs, the eight spaces are indented further than the tab
+    let input = "if x:\n\tif y:\n        z\n";
+    assert_eq!(
+        layout(input, 4),
+        vec![
+            TokenType::INDENT,
+            TokenType::INDENT,
+            TokenType::DEDENT,
+            TokenType::DEDENT,
+        ]
+    );
+
+    // With 8 column tabs, they are the same column but not the same indentation
+    let options = TokenizeOptions {
+        tab_size: 8,
+        ..TokenizeOptions::default()
+    };
+    assert_eq!(
+        tokenize_with_options(input, &options),
+        Err(TokenizeError::new(
+            input,
+            "inconsistent use of tabs and spaces in indentation",
+            13,
+        ))
+    );
+    assert_eq!(TokenizeOptions::default().tab_size, 8);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn test_tokenize_mixed_tabs_and_spaces() {
+    let inconsistent = |input, offset| {
+        Err(TokenizeError::new(
+            input,
+            "inconsistent use of tabs and spaces in indentation",
+            offset,
+        ))
+    };
+
+    // A tab and eight spaces are the same column, but not the same indentation
+    let input = "if x:\n\ty\n        z\n";
+    assert_eq!(tokenize(input), inconsistent(input, 9));
+
+    // The same applies when returning to an outer level
+    let input = "if x:\n\tif y:\n\t\tz\n        w\n";
+    assert_eq!(tokenize(input), inconsistent(input, 17));
+
+    let input = "if x:\n\ty\n\tz\n";
+    assert!(tokenize(input).is_ok());
+    let input = "if x:\n    y\n    z\n";
+    assert!(tokenize(input).is_ok());
 }